@@ -0,0 +1,341 @@
+// Compares the chain reaction of Bomberman::play, backed by the grid index and the
+// activation queue, with the previous implementation that scanned every entity vector
+// Run it with: cargo bench
+use ej_individual::bomberman::Bomberman;
+use ej_individual::utils::point::Point;
+use std::time::{Duration, Instant};
+
+const LEGACY_SIZES: [u32; 3] = [50, 100, 200];
+const INDEXED_ONLY_SIZES: [u32; 1] = [500];
+const ENEMIES: [&str; 3] = ["F1", "F2", "F3"];
+
+// Build a size x size maze with walls on odd coordinates, rows of bombs that chain
+// into each other and enemies with 1 to 3 health between them
+// Enemies with more health are hit by several bombs in different turns
+fn build_maze(size: u32) -> String {
+    let mut maze = String::new();
+    for y in 0..size {
+        let line: Vec<&str> = (0..size)
+            .map(|x| match (x % 4, y % 2) {
+                (_, 1) if x % 2 == 1 => "W",
+                (0, 0) => "B4",
+                (2, 0) => ENEMIES[((x / 4 + y / 2) % 3) as usize],
+                _ => "_",
+            })
+            .collect();
+        maze.push_str(&line.join(" "));
+        maze.push('\n');
+    }
+    maze
+}
+
+// Previous implementation of the chain reaction, before the grid index and the
+// activation queue
+// Every turn scans the bombs for the next active one, every explosion scans the
+// obstacles for each square and every affected point scans all the enemies and bombs
+mod legacy {
+    use ej_individual::maze_placeable::bomb_type::BombType;
+    use ej_individual::maze_placeable::obstacle::Obstacle;
+    use ej_individual::utils::direction::Direction;
+    use ej_individual::utils::maze_display::MazeDisplay;
+    use ej_individual::utils::point::Point;
+    use std::collections::HashSet;
+    use std::fmt::Display;
+
+    pub trait CanBeHit {
+        fn hit(&mut self);
+        fn in_position(&self, position: Point) -> bool;
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum BombState {
+        Idle,
+        Exploded,
+        Activated,
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum EnemyState {
+        Hit,
+        Dead,
+        Idle,
+    }
+
+    struct Bomb {
+        bomb_type: BombType,
+        bomb_state: BombState,
+        position: Point,
+        explosion_distance: u32,
+    }
+
+    impl Bomb {
+        fn new(square: &str, position: Point) -> Bomb {
+            Bomb {
+                bomb_type: BombType::new(square).unwrap(),
+                bomb_state: BombState::Idle,
+                position,
+                explosion_distance: square[1..].parse::<u32>().unwrap(),
+            }
+        }
+
+        fn is_active(&self) -> bool {
+            self.bomb_state == BombState::Activated
+        }
+
+        fn explode(&mut self, maze_size: u32, obstacles: &[Obstacle]) -> Vec<Point> {
+            self.bomb_state = BombState::Exploded;
+            let mut explosion_points = HashSet::from([self.position]);
+
+            for dir in Direction::iter() {
+                let mut move_dir = dir;
+                let mut affected_point = self.position;
+                for _ in 0..self.explosion_distance {
+                    affected_point = match affected_point.next_point(move_dir, maze_size, maze_size)
+                    {
+                        Ok(x) => x,
+                        Err(_) => break,
+                    };
+
+                    let obstacle = obstacles
+                        .iter()
+                        .find(|obstacle| obstacle.is_in_position(affected_point));
+
+                    match obstacle {
+                        Some(obstacle) if obstacle.explosion_can_pass(self.bomb_type) => {
+                            move_dir = obstacle.next_direction(move_dir);
+                            explosion_points.insert(affected_point);
+                        }
+                        Some(_) => break,
+                        None => {
+                            explosion_points.insert(affected_point);
+                        }
+                    }
+                }
+            }
+            Vec::from_iter(explosion_points)
+        }
+    }
+
+    impl CanBeHit for Bomb {
+        fn hit(&mut self) {
+            if self.bomb_state == BombState::Idle {
+                self.bomb_state = BombState::Activated
+            }
+        }
+
+        fn in_position(&self, position: Point) -> bool {
+            self.position == position
+        }
+    }
+
+    impl MazeDisplay for Bomb {
+        fn display(&self) -> String {
+            if self.bomb_state == BombState::Exploded {
+                return "_".to_string();
+            }
+            match self.bomb_type {
+                BombType::Normal => format!("B{}", self.explosion_distance),
+                BombType::Penetrating => format!("S{}", self.explosion_distance),
+            }
+        }
+
+        fn get_position(&self) -> Point {
+            self.position
+        }
+    }
+
+    struct Enemy {
+        health: u32,
+        position: Point,
+        state: EnemyState,
+    }
+
+    impl Enemy {
+        fn new(square: &str, position: Point) -> Enemy {
+            Enemy {
+                health: square[1..].parse::<u32>().unwrap(),
+                position,
+                state: EnemyState::Idle,
+            }
+        }
+
+        fn reset_state(&mut self) {
+            if self.state == EnemyState::Hit {
+                self.state = EnemyState::Idle;
+            }
+        }
+    }
+
+    impl CanBeHit for Enemy {
+        fn hit(&mut self) {
+            match self.state {
+                EnemyState::Idle => {
+                    self.health -= 1;
+                    if self.health == 0 {
+                        self.state = EnemyState::Dead;
+                    } else {
+                        self.state = EnemyState::Hit;
+                    }
+                }
+                EnemyState::Hit => (),
+                EnemyState::Dead => (),
+            }
+        }
+
+        fn in_position(&self, position: Point) -> bool {
+            self.position == position
+        }
+    }
+
+    impl MazeDisplay for Enemy {
+        fn display(&self) -> String {
+            if self.state == EnemyState::Dead {
+                return "_".to_string();
+            }
+            format!("F{}", self.health)
+        }
+
+        fn get_position(&self) -> Point {
+            self.position
+        }
+    }
+
+    pub struct Bomberman {
+        enemies: Vec<Enemy>,
+        bombs: Vec<Bomb>,
+        obstacles: Vec<Obstacle>,
+        size: u32,
+    }
+
+    impl Bomberman {
+        pub fn new(file_string: &str) -> Bomberman {
+            let lines: Vec<&str> = file_string.trim().split('\n').collect();
+            let mut game = Bomberman {
+                enemies: Vec::new(),
+                bombs: Vec::new(),
+                obstacles: Vec::new(),
+                size: lines.len() as u32,
+            };
+            for (y, line) in lines.iter().enumerate() {
+                for (x, square) in line.trim().split(' ').enumerate() {
+                    let point = Point::new(x as u32, y as u32);
+                    match &square[..1] {
+                        "F" => game.enemies.push(Enemy::new(square, point)),
+                        "B" | "S" => game.bombs.push(Bomb::new(square, point)),
+                        "_" => (),
+                        _ => game.obstacles.push(Obstacle::new(square, point).unwrap()),
+                    }
+                }
+            }
+            game
+        }
+
+        fn next_turn(&mut self) {
+            self.enemies.iter_mut().for_each(Enemy::reset_state);
+        }
+
+        fn get_hittable_in_position(&mut self, position: Point) -> Option<&mut dyn CanBeHit> {
+            let mut hittable: Option<&mut dyn CanBeHit> = None;
+            if let Some(enemy) = self
+                .enemies
+                .iter_mut()
+                .find(|enemy| enemy.in_position(position))
+            {
+                hittable = Some(enemy);
+            }
+            if let Some(bomb) = self
+                .bombs
+                .iter_mut()
+                .find(|bomb| bomb.in_position(position))
+            {
+                hittable = Some(bomb);
+            }
+            hittable
+        }
+
+        pub fn play(&mut self, start_bomb: Point) -> String {
+            if let Some(bomb) = self
+                .bombs
+                .iter_mut()
+                .find(|bomb| bomb.in_position(start_bomb))
+            {
+                bomb.hit();
+            }
+
+            while let Some(bomb) = self.bombs.iter_mut().find(|bomb| bomb.is_active()) {
+                let afected_positions = bomb.explode(self.size, &self.obstacles);
+                for position in afected_positions {
+                    if let Some(hittable) = self.get_hittable_in_position(position) {
+                        hittable.hit();
+                    }
+                }
+                self.next_turn();
+            }
+
+            self.to_string()
+        }
+
+        fn get_all_displayable(&self) -> Vec<&dyn MazeDisplay> {
+            let mut displayable: Vec<&dyn MazeDisplay> = Vec::new();
+            displayable.extend(self.enemies.iter().map(|enemy| enemy as &dyn MazeDisplay));
+            displayable.extend(self.bombs.iter().map(|bomb| bomb as &dyn MazeDisplay));
+            displayable.extend(
+                self.obstacles
+                    .iter()
+                    .map(|obstacle| obstacle as &dyn MazeDisplay),
+            );
+            displayable
+        }
+
+        fn to_matrix(&self) -> Vec<Vec<String>> {
+            let mut matrix = vec![vec!["_".to_string(); self.size as usize]; self.size as usize];
+            for displayable in self.get_all_displayable() {
+                let position = displayable.get_position();
+                matrix[position.y as usize][position.x as usize] = displayable.display();
+            }
+            matrix
+        }
+    }
+
+    impl Display for Bomberman {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            let mut display = String::new();
+            for line in self.to_matrix() {
+                display.push_str(&line.join(" "));
+                display.push('\n');
+            }
+            write!(f, "{display}")
+        }
+    }
+}
+
+fn play_legacy(maze: &str) -> String {
+    legacy::Bomberman::new(maze).play(Point::new(0, 0))
+}
+
+fn play_indexed(maze: &str) -> String {
+    let mut game = Bomberman::new(maze).unwrap();
+    game.play(Point::new(0, 0)).unwrap()
+}
+
+fn time<F: FnOnce() -> String>(f: F) -> (String, Duration) {
+    let start = Instant::now();
+    let board = f();
+    (board, start.elapsed())
+}
+
+fn main() {
+    println!("{:>6} {:>14} {:>14}", "size", "legacy", "indexed");
+    for size in LEGACY_SIZES {
+        let maze = build_maze(size);
+        let (legacy_board, legacy) = time(|| play_legacy(&maze));
+        let (indexed_board, indexed) = time(|| play_indexed(&maze));
+        assert_eq!(legacy_board, indexed_board, "boards differ for size {size}");
+        println!("{size:>6} {legacy:>14.2?} {indexed:>14.2?}");
+    }
+    for size in INDEXED_ONLY_SIZES {
+        let maze = build_maze(size);
+        let (_, indexed) = time(|| play_indexed(&maze));
+        println!("{size:>6} {:>14} {indexed:>14.2?}", "-");
+    }
+}
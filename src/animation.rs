@@ -0,0 +1,318 @@
+use crate::bomberman::Bomberman;
+use crate::bomberman_errors::BombermanError;
+use crate::bomberman_events::BombermanEvent;
+use crate::gif;
+use crate::maze_placeable::bomb_type::{NORMAL_BOMB, PENETRATING_BOMB};
+use crate::maze_placeable::enemy::ENEMY;
+use crate::maze_placeable::obstacle_type::{
+    BREAKABLE, MIRROR_BACKSLASH, MIRROR_SLASH, REDIRECTION_DOWN, REDIRECTION_LEFT,
+    REDIRECTION_RIGHT, REDIRECTION_UP, ROCK, SPLITTER, TELEPORTER, WALL,
+};
+use crate::simulation::Simulation;
+use crate::utils::point::Point;
+use std::fmt::Display;
+
+// Largest size in pixels of each square, big mazes use smaller squares
+const MAX_CELL_SIZE: u32 = 16;
+// Largest width or height in pixels of the frames, unless the maze has more squares
+const MAX_IMAGE_SIZE: u32 = 2048;
+
+// Time each frame is shown, in hundredths of a second
+pub const FRAME_DELAY: u16 = 50;
+
+const BACKGROUND: u8 = 0;
+const GRID: u8 = 1;
+const WALL_COLOR: u8 = 2;
+const ROCK_COLOR: u8 = 3;
+const REDIRECTION_COLOR: u8 = 4;
+const NORMAL_BOMB_COLOR: u8 = 5;
+const PENETRATING_BOMB_COLOR: u8 = 6;
+const ENEMY_COLOR: u8 = 7;
+const BLAST_COLOR: u8 = 8;
+const FLASH_COLOR: u8 = 9;
+const TELEPORTER_COLOR: u8 = 10;
+
+// Colors of the frames, same as the SVG images
+const PALETTE: [[u8; 3]; 11] = [
+    [0xf4, 0xf1, 0xe8],
+    [0xd8, 0xd2, 0xc0],
+    [0x6b, 0x6b, 0x6b],
+    [0x8b, 0x5a, 0x2b],
+    [0x2f, 0x6f, 0xb0],
+    [0x22, 0x22, 0x22],
+    [0xb0, 0x22, 0x22],
+    [0x2e, 0x8b, 0x3a],
+    [0xff, 0x8c, 0x00],
+    [0xff, 0xf1, 0x4d],
+    [0x8e, 0x44, 0xad],
+];
+
+// Reason the chain reaction could not be animated
+#[derive(Debug, PartialEq)]
+pub enum AnimationError {
+    // Errors of the game, one for each starting position without a bomb
+    Maze(Vec<BombermanError>),
+    // The frames would be bigger than a GIF can be, in pixels
+    TooLarge { width: u32, height: u32 },
+}
+
+impl Display for AnimationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            AnimationError::Maze(errors) => {
+                let errors: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+                write!(f, "{}", errors.join("\n"))
+            }
+            AnimationError::TooLarge { width, height } => write!(
+                f,
+                "the maze needs a {width}x{height} pixels GIF, it can be at most {}x{} pixels",
+                u16::MAX,
+                u16::MAX
+            ),
+        }
+    }
+}
+
+// Play the chain reaction of a copy of the game and return it as an animated GIF
+// The first frame is the maze before the reaction and the last one the maze after it
+// In between there is a frame for each exploding bomb, showing the board before its turn
+// with the squares hit by the bomb in orange and the enemies it hits flashing in yellow
+// Each frame is encoded as soon as it is drawn, so only the compressed frames are kept
+// Return an error for each starting position without a bomb, or if the maze is too big
+// for a GIF even with squares of 1 pixel
+pub fn animate(game: &Bomberman, start_bombs: &[Point]) -> Result<Vec<u8>, AnimationError> {
+    let (width, height) = (game.width(), game.height());
+    let cell = (MAX_IMAGE_SIZE / width.max(height).max(1)).clamp(1, MAX_CELL_SIZE);
+    let canvas = Canvas::new(width * cell, height * cell, cell);
+    let (Ok(gif_width), Ok(gif_height)) =
+        (u16::try_from(canvas.width), u16::try_from(canvas.height))
+    else {
+        return Err(AnimationError::TooLarge {
+            width: canvas.width,
+            height: canvas.height,
+        });
+    };
+    let steps =
+        Simulation::with_start_bombs(game.clone(), start_bombs).map_err(AnimationError::Maze)?;
+
+    let mut encoder = gif::Encoder::new(gif_width, gif_height, &PALETTE, FRAME_DELAY);
+    let mut board = game.to_string();
+    encoder.add_frame(&canvas.draw(&board, &[], &[]));
+    for step in steps {
+        for (blast, hit) in explosions(&step.events) {
+            encoder.add_frame(&canvas.draw(&board, &blast, &hit));
+        }
+        board = step.board;
+    }
+    encoder.add_frame(&canvas.draw(&board, &[], &[]));
+    Ok(encoder.finish())
+}
+
+// Split the events of a turn by exploding bomb
+// Return the squares hit by each bomb and the position of the enemies it hit
+fn explosions(events: &[BombermanEvent]) -> Vec<(Vec<Point>, Vec<Point>)> {
+    let mut explosions: Vec<(Vec<Point>, Vec<Point>)> = Vec::new();
+    for event in events {
+        match (event, explosions.last_mut()) {
+            (BombermanEvent::BombExploded { affected, .. }, _) => {
+                explosions.push((affected.clone(), Vec::new()))
+            }
+            (
+                BombermanEvent::EnemyHit { position, .. } | BombermanEvent::EnemyKilled(position),
+                Some((_, hit)),
+            ) => hit.push(*position),
+            _ => (),
+        }
+    }
+    explosions
+}
+
+// Draws boards as indexes into the palette
+struct Canvas {
+    width: u32,
+    height: u32,
+    cell: u32,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32, cell: u32) -> Canvas {
+        Canvas {
+            width,
+            height,
+            cell,
+        }
+    }
+
+    // Draw the board from its text, one token for each square
+    fn draw(&self, board: &str, blast: &[Point], flashing: &[Point]) -> Vec<u8> {
+        let mut pixels = vec![BACKGROUND; (self.width * self.height) as usize];
+        for point in blast {
+            self.fill(&mut pixels, *point, |_, _| Some(BLAST_COLOR));
+        }
+        for (y, line) in board.lines().enumerate() {
+            for (x, token) in line.split(' ').enumerate() {
+                let point = Point::new(x as u32, y as u32);
+                self.draw_square(&mut pixels, token, point, flashing.contains(&point));
+            }
+        }
+        pixels
+    }
+
+    fn draw_square(&self, pixels: &mut [u8], token: &str, point: Point, flashing: bool) {
+        let size = self.cell as i64;
+        let center = (size - 1) as f64 / 2.0;
+        let in_circle = move |x: u32, y: u32, radius: f64| {
+            let (dx, dy) = (x as f64 - center, y as f64 - center);
+            dx * dx + dy * dy <= radius * radius
+        };
+        // Shape of the square in coordinates inside it, None leaves the pixel as it is
+        let shape: Box<dyn Fn(u32, u32) -> Option<u8>> = match token {
+            WALL => Box::new(|_, _| Some(WALL_COLOR)),
+            ROCK => Box::new(move |x, y| in_circle(x, y, center + 0.5).then_some(ROCK_COLOR)),
+            REDIRECTION_UP | REDIRECTION_DOWN | REDIRECTION_LEFT | REDIRECTION_RIGHT => {
+                let token = token.to_string();
+                Box::new(move |x, y| {
+                    // Triangle pointing up, rotated to the direction of the redirection
+                    let (along, across) = match token.as_str() {
+                        REDIRECTION_UP => (y as i64, x as i64),
+                        REDIRECTION_DOWN => (size - 1 - y as i64, x as i64),
+                        REDIRECTION_LEFT => (x as i64, y as i64),
+                        _ => (size - 1 - x as i64, y as i64),
+                    };
+                    let half_width = along / 2;
+                    ((across - size / 2).abs() <= half_width).then_some(REDIRECTION_COLOR)
+                })
+            }
+            // Mirrors are a diagonal band with the slope of their token
+            MIRROR_SLASH => Box::new(move |x, y| {
+                ((x as i64 + y as i64 - (size - 1)).abs() <= 1).then_some(REDIRECTION_COLOR)
+            }),
+            MIRROR_BACKSLASH => Box::new(move |x, y| {
+                ((x as i64 - y as i64).abs() <= 1).then_some(REDIRECTION_COLOR)
+            }),
+            // Splitters are a cross through the center
+            SPLITTER => Box::new(move |x, y| {
+                ((x as i64 - size / 2).abs() <= 1 || (y as i64 - size / 2).abs() <= 1)
+                    .then_some(REDIRECTION_COLOR)
+            }),
+            // Teleporters are a ring
+            _ if token.starts_with(TELEPORTER) => Box::new(move |x, y| {
+                (in_circle(x, y, center + 0.5) && !in_circle(x, y, center * 0.5))
+                    .then_some(TELEPORTER_COLOR)
+            }),
+            // Breakable blocks are checkered with the colour of the rocks
+            _ if token.starts_with(BREAKABLE) => {
+                let tile = (self.cell / 4).max(1);
+                Box::new(move |x, y| {
+                    (x / tile + y / tile)
+                        .is_multiple_of(2)
+                        .then_some(ROCK_COLOR)
+                })
+            }
+            _ if token.starts_with(NORMAL_BOMB) => {
+                Box::new(move |x, y| in_circle(x, y, center * 0.8).then_some(NORMAL_BOMB_COLOR))
+            }
+            _ if token.starts_with(PENETRATING_BOMB) => Box::new(move |x, y| {
+                in_circle(x, y, center * 0.8).then_some(PENETRATING_BOMB_COLOR)
+            }),
+            _ if token.starts_with(ENEMY) => {
+                let color = if flashing { FLASH_COLOR } else { ENEMY_COLOR };
+                Box::new(move |x, y| in_circle(x, y, center * 0.7).then_some(color))
+            }
+            _ => Box::new(|x, y| (x == 0 || y == 0).then_some(GRID)),
+        };
+        self.fill(pixels, point, shape);
+    }
+
+    // Color the pixels of the square in the point with the shape
+    fn fill(&self, pixels: &mut [u8], point: Point, shape: impl Fn(u32, u32) -> Option<u8>) {
+        for y in 0..self.cell {
+            for x in 0..self.cell {
+                let (pixel_x, pixel_y) = (point.x * self.cell + x, point.y * self.cell + y);
+                if pixel_x >= self.width || pixel_y >= self.height {
+                    continue;
+                }
+                if let Some(color) = shape(x, y) {
+                    pixels[(pixel_y * self.width + pixel_x) as usize] = color;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_explosions_group_hits_by_bomb() {
+        let events = vec![
+            BombermanEvent::BombActivated(Point::new(0, 0)),
+            BombermanEvent::BombExploded {
+                position: Point::new(0, 0),
+                affected: vec![Point::new(0, 0), Point::new(1, 0)],
+            },
+            BombermanEvent::EnemyKilled(Point::new(1, 0)),
+            BombermanEvent::BombExploded {
+                position: Point::new(2, 0),
+                affected: vec![Point::new(2, 0)],
+            },
+        ];
+        assert_eq!(
+            explosions(&events),
+            vec![
+                (
+                    vec![Point::new(0, 0), Point::new(1, 0)],
+                    vec![Point::new(1, 0)]
+                ),
+                (vec![Point::new(2, 0)], vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_draw_colors_each_square() {
+        let canvas = Canvas::new(32, 16, 16);
+        let pixels = canvas.draw("W F1\n", &[Point::new(1, 0)], &[Point::new(1, 0)]);
+        let pixel = |x: u32, y: u32| pixels[(y * 32 + x) as usize];
+        assert_eq!(pixel(0, 0), WALL_COLOR);
+        assert_eq!(pixel(24, 8), FLASH_COLOR);
+        assert_eq!(pixel(17, 1), BLAST_COLOR);
+    }
+
+    #[test]
+    fn test_animate_has_a_frame_for_each_bomb() {
+        let game = Bomberman::new("B2 B1 F1\n_ _ _\n").unwrap();
+        let gif = animate(&game, &[Point::new(0, 0)]).unwrap();
+        // Before, one frame for each of the 2 bombs and after
+        let frames = gif
+            .windows(4)
+            .filter(|w| w == &[0x21, 0xF9, 0x04, 0x00])
+            .count();
+        assert_eq!(frames, 4);
+        assert_eq!(&gif[6..10], &[48, 0, 32, 0]);
+        assert_eq!(game.bomb_positions().len(), 2);
+    }
+
+    #[test]
+    fn test_animate_without_start_bomb_is_error() {
+        let game = Bomberman::new("B2 _\n").unwrap();
+        assert!(matches!(
+            animate(&game, &[Point::new(1, 0)]),
+            Err(AnimationError::Maze(_))
+        ));
+    }
+
+    #[test]
+    fn test_animate_too_large_maze_is_error() {
+        let maze = format!("B1{}\n", " _".repeat(70000));
+        let game = Bomberman::new(&maze).unwrap();
+        assert_eq!(
+            animate(&game, &[Point::new(0, 0)]),
+            Err(AnimationError::TooLarge {
+                width: 70001,
+                height: 1
+            })
+        );
+    }
+}
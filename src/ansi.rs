@@ -0,0 +1,187 @@
+use crate::maze_placeable::bomb_type::{NORMAL_BOMB, PENETRATING_BOMB};
+use crate::maze_placeable::enemy::ENEMY;
+use crate::maze_placeable::obstacle_type::{
+    BREAKABLE, MIRROR_BACKSLASH, MIRROR_SLASH, REDIRECTION, ROCK, SPLITTER, TELEPORTER, WALL,
+};
+use crate::utils::maze_display::MazeDisplay;
+use crate::utils::point::Point;
+use std::io::Read;
+
+// First byte of the escape sequences of the terminal
+pub const ESCAPE: u8 = 0x1b;
+pub const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+pub const HIDE_CURSOR: &str = "\x1b[?25l";
+pub const SHOW_CURSOR: &str = "\x1b[?25h";
+
+pub const RESET: &str = "\x1b[0m";
+// Squares that changed between two boards, in the board before and after
+pub const DIFF_BEFORE: &str = "\x1b[1;41m";
+pub const DIFF_AFTER: &str = "\x1b[1;42m";
+const GREY: &str = "\x1b[90m";
+const BROWN: &str = "\x1b[38;5;130m";
+const YELLOW: &str = "\x1b[33m";
+const BLUE: &str = "\x1b[34m";
+const RED: &str = "\x1b[1;31m";
+const GREEN: &str = "\x1b[1;32m";
+const MAGENTA: &str = "\x1b[35m";
+const BLAST_BACKGROUND: &str = "\x1b[48;5;208m";
+const SELECTED: &str = "\x1b[7m";
+
+// Keys understood by the terminal viewer
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    Select,
+    Quit,
+    Other,
+}
+
+// Read a key from a terminal in raw mode
+// Arrows are escape sequences, wasd and hjkl also move. Enter and space select, q and Ctrl-C quit
+// An Esc that the input does not follow with the rest of a sequence also quits
+pub fn read_key(input: &mut impl Read) -> std::io::Result<Key> {
+    let mut byte = [0; 1];
+    input.read_exact(&mut byte)?;
+    let key = match byte[0] {
+        ESCAPE => {
+            let mut sequence = [0; 2];
+            match input.read_exact(&mut sequence) {
+                Ok(_) => match sequence {
+                    [b'[', b'A'] => Key::Up,
+                    [b'[', b'B'] => Key::Down,
+                    [b'[', b'C'] => Key::Right,
+                    [b'[', b'D'] => Key::Left,
+                    _ => Key::Other,
+                },
+                Err(_) => Key::Quit,
+            }
+        }
+        b'w' | b'k' => Key::Up,
+        b's' | b'j' => Key::Down,
+        b'a' | b'h' => Key::Left,
+        b'd' | b'l' => Key::Right,
+        b'\n' | b'\r' | b' ' => Key::Select,
+        // Ctrl-C does not stop the program in raw mode
+        b'q' | 0x03 => Key::Quit,
+        _ => Key::Other,
+    };
+    Ok(key)
+}
+
+// Render a width x height maze with a colour for each kind of object:
+// walls grey, rocks brown, breakable blocks yellow, redirections blue, bombs red and
+// enemies green with their health
+// The selected square is shown in reverse video and the blast squares with an orange background
+pub fn render(
+    width: u32,
+    height: u32,
+    objects: &[&dyn MazeDisplay],
+    selected: Option<Point>,
+    blast: &[Point],
+) -> String {
+    let mut tokens = vec![vec!["_".to_string(); width as usize]; height as usize];
+    for object in objects {
+        let position = object.get_position();
+        if let Some(square) = tokens
+            .get_mut(position.y as usize)
+            .and_then(|line| line.get_mut(position.x as usize))
+        {
+            *square = object.display();
+        }
+    }
+    // Every square is as wide as the longest token so the columns line up
+    let square_width = tokens.iter().flatten().map(String::len).max().unwrap_or(1);
+
+    let mut screen = String::new();
+    for (y, line) in tokens.iter().enumerate() {
+        for (x, token) in line.iter().enumerate() {
+            let point = Point::new(x as u32, y as u32);
+            if selected == Some(point) {
+                screen.push_str(SELECTED);
+            }
+            if blast.contains(&point) {
+                screen.push_str(BLAST_BACKGROUND);
+            }
+            screen.push_str(color(token));
+            screen.push_str(&format!("{token:<square_width$}"));
+            screen.push_str(RESET);
+            if x + 1 < line.len() {
+                screen.push(' ');
+            }
+        }
+        // Raw mode does not move to the start of the line on \n
+        screen.push_str("\r\n");
+    }
+    screen
+}
+
+fn color(token: &str) -> &'static str {
+    match token {
+        WALL => GREY,
+        ROCK => BROWN,
+        MIRROR_SLASH | MIRROR_BACKSLASH | SPLITTER => BLUE,
+        _ if token.starts_with(REDIRECTION) => BLUE,
+        _ if token.starts_with(BREAKABLE) => YELLOW,
+        _ if token.starts_with(TELEPORTER) => MAGENTA,
+        _ if token.starts_with(NORMAL_BOMB) || token.starts_with(PENETRATING_BOMB) => RED,
+        _ if token.starts_with(ENEMY) => GREEN,
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::maze_placeable::bomb::Bomb;
+    use crate::maze_placeable::enemy::Enemy;
+    use crate::maze_placeable::obstacle::Obstacle;
+
+    #[test]
+    fn test_read_key_parses_arrows_and_letters() {
+        let mut input: &[u8] = b"\x1b[A\x1b[Dj q\x1b[Zx";
+        let keys: Vec<Key> = (0..7).map(|_| read_key(&mut input).unwrap()).collect();
+        assert_eq!(
+            keys,
+            vec![
+                Key::Up,
+                Key::Left,
+                Key::Down,
+                Key::Select,
+                Key::Quit,
+                Key::Other,
+                Key::Other
+            ]
+        );
+        assert!(read_key(&mut input).is_err());
+    }
+
+    #[test]
+    fn test_read_key_lone_escape_quits() {
+        let mut input: &[u8] = b"\x1b";
+        assert_eq!(read_key(&mut input).ok(), Some(Key::Quit));
+    }
+
+    #[test]
+    fn test_render_colours_each_object() {
+        let wall = Obstacle::new("W", Point::new(0, 0)).unwrap();
+        let bomb = Bomb::new("B3:2", Point::new(1, 0)).unwrap();
+        let enemy = Enemy::new("F2", Point::new(0, 1)).unwrap();
+        let objects: Vec<&dyn MazeDisplay> = vec![&wall, &bomb, &enemy];
+        let screen = render(2, 2, &objects, None, &[]);
+        let expected =
+            format!("{GREY}W   {RESET} {RED}B3:2{RESET}\r\n{GREEN}F2  {RESET} _   {RESET}\r\n");
+        assert_eq!(screen, expected);
+    }
+
+    #[test]
+    fn test_render_marks_selected_and_blast_squares() {
+        let screen = render(2, 1, &[], Some(Point::new(0, 0)), &[Point::new(1, 0)]);
+        assert_eq!(
+            screen,
+            format!("{SELECTED}_{RESET} {BLAST_BACKGROUND}_{RESET}\r\n")
+        );
+    }
+}
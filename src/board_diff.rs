@@ -0,0 +1,215 @@
+use crate::ansi;
+use crate::maze_placeable::bomb_type::{NORMAL_BOMB, PENETRATING_BOMB};
+use crate::maze_placeable::enemy::ENEMY;
+use crate::utils::point::Point;
+use std::fmt::Display;
+
+const EMPTY: &str = "_";
+
+// Tokens of a board, row by row
+type Tokens<'a> = Vec<Vec<&'a str>>;
+
+// What happened to a square between two boards
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Change {
+    Killed,
+    Damaged,
+    Exploded,
+    Removed,
+    Added,
+    Changed,
+}
+
+impl Change {
+    // Classify the change of a square from its tokens
+    fn new(before: &str, after: &str) -> Change {
+        let is_enemy = |token: &str| token.starts_with(ENEMY);
+        let is_bomb =
+            |token: &str| token.starts_with(NORMAL_BOMB) || token.starts_with(PENETRATING_BOMB);
+        let health = |token: &str| token[ENEMY.len()..].parse::<u32>().ok();
+        match (before, after) {
+            (EMPTY, _) => Change::Added,
+            (before, EMPTY) if is_enemy(before) => Change::Killed,
+            (before, EMPTY) if is_bomb(before) => Change::Exploded,
+            (_, EMPTY) => Change::Removed,
+            (before, after) if is_enemy(before) && is_enemy(after) => {
+                match (health(before), health(after)) {
+                    (Some(before), Some(after)) if after < before => Change::Damaged,
+                    _ => Change::Changed,
+                }
+            }
+            _ => Change::Changed,
+        }
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let name = match self {
+            Change::Killed => "killed",
+            Change::Damaged => "damaged",
+            Change::Exploded => "exploded",
+            Change::Removed => "removed",
+            Change::Added => "added",
+            Change::Changed => "changed",
+        };
+        write!(f, "{name}")
+    }
+}
+
+// A square that is different in two boards, with its token in each one
+#[derive(Debug, PartialEq, Clone)]
+pub struct CellChange {
+    pub point: Point,
+    pub before: String,
+    pub after: String,
+    pub change: Change,
+}
+
+impl Display for CellChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} -> {} ({})",
+            self.point, self.before, self.after, self.change
+        )
+    }
+}
+
+// Split a board in the tokens of each row
+fn tokens(board: &str) -> Tokens<'_> {
+    board
+        .lines()
+        .map(|line| line.split_whitespace().collect())
+        .collect()
+}
+
+// Return the tokens of both boards, or an error if they do not have the same size
+fn same_size_tokens<'a>(
+    before: &'a str,
+    after: &'a str,
+) -> Result<(Tokens<'a>, Tokens<'a>), String> {
+    let (before, after) = (tokens(before), tokens(after));
+    let size = |board: &Tokens| (board.first().map_or(0, Vec::len), board.len());
+    let same_rows = before.len() == after.len()
+        && before
+            .iter()
+            .zip(&after)
+            .all(|(before, after)| before.len() == after.len());
+    if !same_rows {
+        let ((before_width, before_height), (after_width, after_height)) =
+            (size(&before), size(&after));
+        return Err(format!(
+            "The boards have different sizes, {before_width}x{before_height} and {after_width}x{after_height}"
+        ));
+    }
+    Ok((before, after))
+}
+
+// Compare two boards square by square and return the squares that changed, row by row
+// Return an error if the boards do not have the same size
+pub fn diff(before: &str, after: &str) -> Result<Vec<CellChange>, String> {
+    let (before, after) = same_size_tokens(before, after)?;
+    let mut changes = Vec::new();
+    for (y, (before_row, after_row)) in before.iter().zip(&after).enumerate() {
+        for (x, (before, after)) in before_row.iter().zip(after_row).enumerate() {
+            if before != after {
+                changes.push(CellChange {
+                    point: Point::new(x as u32, y as u32),
+                    before: before.to_string(),
+                    after: after.to_string(),
+                    change: Change::new(before, after),
+                });
+            }
+        }
+    }
+    Ok(changes)
+}
+
+// Show both boards next to each other with the changed squares coloured,
+// red in the board before and green in the board after
+pub fn side_by_side(before: &str, after: &str) -> Result<String, String> {
+    let (before, after) = same_size_tokens(before, after)?;
+    // Every square is as wide as the longest token so the columns line up
+    let square_width = before
+        .iter()
+        .chain(&after)
+        .flatten()
+        .map(|token| token.len())
+        .max()
+        .unwrap_or(1);
+    let render_row = |row: &[&str], other: &[&str], color: &str| {
+        let squares: Vec<String> = row
+            .iter()
+            .zip(other)
+            .map(|(token, other)| {
+                let square = format!("{token:<square_width$}");
+                if token == other {
+                    square
+                } else {
+                    format!("{color}{square}{}", ansi::RESET)
+                }
+            })
+            .collect();
+        squares.join(" ")
+    };
+
+    let mut view = String::new();
+    for (before_row, after_row) in before.iter().zip(&after) {
+        let line = format!(
+            "{} | {}",
+            render_row(before_row, after_row, ansi::DIFF_BEFORE),
+            render_row(after_row, before_row, ansi::DIFF_AFTER)
+        );
+        view.push_str(line.trim_end());
+        view.push('\n');
+    }
+    Ok(view)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_diff_reports_each_changed_square() {
+        let changes = diff("B1 F2 F1\n_ W R\n", "_ F1 _\n_ W R\n").unwrap();
+        let lines: Vec<String> = changes.iter().map(|change| change.to_string()).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "(0, 0): B1 -> _ (exploded)",
+                "(1, 0): F2 -> F1 (damaged)",
+                "(2, 0): F1 -> _ (killed)"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_of_same_board_is_empty() {
+        assert_eq!(diff("B1 _\n", "B1 _\n"), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_diff_of_boards_with_different_size_is_error() {
+        assert_eq!(
+            diff("_ _\n", "_ _\n_ _\n"),
+            Err("The boards have different sizes, 2x1 and 2x2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_side_by_side_colours_changed_squares() {
+        let view = side_by_side("B1 F1\n", "_ F1\n").unwrap();
+        assert_eq!(
+            view,
+            format!(
+                "{}B1{} F1 | {}_ {} F1\n",
+                ansi::DIFF_BEFORE,
+                ansi::RESET,
+                ansi::DIFF_AFTER,
+                ansi::RESET
+            )
+        );
+    }
+}
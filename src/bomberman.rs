@@ -1,139 +1,492 @@
-use crate::bomberman_errors::BombermanError;
+use crate::ansi;
+use crate::bomberman_errors::{BombermanError, Location, Span};
+use crate::bomberman_events::BombermanEvent;
+use crate::maze_json;
 use crate::maze_placeable::bomb::Bomb;
 use crate::maze_placeable::enemy::Enemy;
 use crate::maze_placeable::obstacle::Obstacle;
 use crate::maze_placeable::obstacle_type::ObstacleType;
 use crate::maze_placeable::{bomb_type, enemy};
+use crate::outcome::Outcome;
+use crate::svg;
 use crate::utils::can_be_hit::CanBeHit;
+use crate::utils::grid::{Grid, Occupant};
 use crate::utils::maze_display::MazeDisplay;
 use crate::utils::point::Point;
+use std::collections::{BTreeMap, VecDeque};
 use std::fmt::Display;
+use std::sync::Arc;
 
-#[derive(Debug)]
+// The obstacles and the grid are shared between clones of the game to keep them cheap
+// The grid does not change while playing, the obstacles are copied the first time
+// a breakable block is hit
+#[derive(Debug, Clone)]
 pub struct Bomberman {
     enemies: Vec<Enemy>,
     bombs: Vec<Bomb>,
-    obstacles: Vec<Obstacle>,
-    size: u32,
+    obstacles: Arc<Vec<Obstacle>>,
+    grid: Arc<Grid>,
+    turn: u32,
+    turns: VecDeque<Vec<usize>>,
+    fuses: BTreeMap<u32, Vec<usize>>,
+    activated_bombs: Vec<usize>,
+    hit_enemies: Vec<usize>,
+    hit_obstacles: Vec<usize>,
 }
 impl Bomberman {
     // Create a new game from a string
-    // The string should be a square matrix of squares separated by spaces
+    // The string should be a rectangular matrix of squares separated by spaces
     pub fn new(file_string: &str) -> Result<Bomberman, BombermanError> {
+        Bomberman::parse_all(file_string, false).map_err(first_error)
+    }
+
+    // Create a new game from a string
+    // The string should be a square matrix of squares separated by spaces,
+    // like the original assignment format
+    pub fn new_strict(file_string: &str) -> Result<Bomberman, BombermanError> {
+        Bomberman::parse_all(file_string, true).map_err(first_error)
+    }
+
+    // Parse the game from a string without stopping at the first error
+    // If square_only is true, every row should have as many columns as the maze has lines
+    // Otherwise every row should have as many columns as the first one
+    // Returns the game or every error found, in the order they appear in the string,
+    // each one with the span of its token in the string. A row with the wrong length is
+    // reported after the errors of its squares
+    // Teleporters without exactly one partner are reported after the other errors
+    pub fn parse_all(
+        file_string: &str,
+        square_only: bool,
+    ) -> Result<Bomberman, Vec<BombermanError>> {
         let lines: Vec<&str> = file_string.trim().split('\n').collect();
+        let height = lines.len() as u32;
+        let width = lines
+            .first()
+            .map_or(0, |line| line.trim().split(' ').count() as u32);
 
-        let mut game = Bomberman {
-            enemies: Vec::new(),
-            bombs: Vec::new(),
-            obstacles: Vec::new(),
-            size: lines.len() as u32,
-        };
+        let mut game = Bomberman::empty(width, height);
+        let mut errors = Vec::new();
 
         for (y, line) in lines.iter().enumerate() {
-            let squares: Vec<&str> = line.trim().split(' ').collect();
-            if squares.len() != game.size as usize {
-                return Err(BombermanError::MazeNotSquare(format!(
-                    "Maze has {} lines and {} columns, it should be equal",
-                    game.size,
-                    squares.len()
-                )));
-            }
+            let line = line.trim();
+            let squares: Vec<&str> = line.split(' ').collect();
+            // Point to the first column out of place, the extra token if there is one
+            // or the end of the line if the row is too short
+            let column = squares.len().min(width as usize);
+            let token = squares.get(column).copied().unwrap_or(&line[line.len()..]);
+            let location = Location::new(Point::new(column as u32, y as u32), token);
+            let row_error = if square_only && squares.len() != height as usize {
+                Some(BombermanError::MazeNotSquare {
+                    lines: height,
+                    columns: squares.len() as u32,
+                    location,
+                })
+            } else if squares.len() != width as usize {
+                Some(BombermanError::MazeNotRectangular {
+                    columns: squares.len() as u32,
+                    expected: width,
+                    location,
+                })
+            } else {
+                None
+            };
             for (x, square) in squares.iter().enumerate() {
                 let point = Point::new(x as u32, y as u32);
-                game.add_square(square, point)?;
+                if let Err(error) = game.add_square(square, point) {
+                    errors.push(error.with_span(Span::of(file_string, square)));
+                }
+            }
+            // The row length is known once the whole row is read, after its squares
+            if let Some(error) = row_error {
+                errors.push(error.with_span(Span::of(file_string, token)));
+            }
+        }
+        for error in game.pair_teleporters() {
+            let span = error.location().and_then(|location| {
+                let line = lines.get(location.position.y as usize)?;
+                let token = line.trim().split(' ').nth(location.position.x as usize)?;
+                Some(Span::of(file_string, token))
+            });
+            errors.push(match span {
+                Some(span) => error.with_span(span),
+                None => error,
+            });
+        }
+
+        if errors.is_empty() {
+            Ok(game)
+        } else {
+            Err(errors)
+        }
+    }
+
+    // Create a new game from its JSON representation, see maze_json for the format
+    // Return an error if the JSON is invalid, a square is invalid, out of the maze
+    // or in the same position as another one
+    pub fn from_json(json: &str) -> Result<Bomberman, BombermanError> {
+        let maze = maze_json::from_json(json)?;
+        let mut game = Bomberman::empty(maze.width, maze.height);
+        for (square, point) in maze.squares {
+            if point.x >= maze.width || point.y >= maze.height {
+                return Err(BombermanError::invalid_square(
+                    point,
+                    &square,
+                    &format!("The square {square} at position {point} is out of the maze"),
+                ));
             }
+            if game.grid.get(point).is_some() {
+                return Err(BombermanError::invalid_square(
+                    point,
+                    &square,
+                    &format!("The square {square} at position {point} is already taken"),
+                ));
+            }
+            game.add_square(&square, point)?;
+        }
+        match game.pair_teleporters().into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(game),
+        }
+    }
+
+    // Return the JSON representation of the maze, like the board exploded bombs,
+    // dead enemies and broken obstacles are left out
+    pub fn to_json(&self) -> String {
+        let bombs: Vec<&Bomb> = self.bombs.iter().filter(|b| !b.is_exploded()).collect();
+        let enemies: Vec<&Enemy> = self.enemies.iter().filter(|e| e.is_alive()).collect();
+        let obstacles: Vec<&Obstacle> = self.obstacles.iter().filter(|o| !o.is_broken()).collect();
+        maze_json::to_json(self.width(), self.height(), &bombs, &enemies, &obstacles)
+    }
+
+    // Return the maze as an SVG image with the highlighted squares overlaid,
+    // see svg::render for how each object is drawn
+    pub fn to_svg(&self, highlighted: &[Point]) -> String {
+        svg::render(
+            self.width(),
+            self.height(),
+            &self.get_all_displayable(),
+            highlighted,
+        )
+    }
+
+    // Return the maze coloured with ANSI escapes for a terminal,
+    // see ansi::render for the colour of each object
+    pub fn to_ansi(&self, selected: Option<Point>, blast: &[Point]) -> String {
+        ansi::render(
+            self.width(),
+            self.height(),
+            &self.get_all_displayable(),
+            selected,
+            blast,
+        )
+    }
+
+    fn empty(width: u32, height: u32) -> Bomberman {
+        Bomberman {
+            enemies: Vec::new(),
+            bombs: Vec::new(),
+            obstacles: Arc::new(Vec::new()),
+            grid: Arc::new(Grid::new(width, height)),
+            turn: 0,
+            turns: VecDeque::new(),
+            fuses: BTreeMap::new(),
+            activated_bombs: Vec::new(),
+            hit_enemies: Vec::new(),
+            hit_obstacles: Vec::new(),
         }
-        Ok(game)
     }
 
-    // Add a square to the game
+    // Pair the teleporters in the grid once the maze is read
+    // Return an error for each teleporter id that does not appear exactly twice,
+    // in the order of the ids
+    fn pair_teleporters(&mut self) -> Vec<BombermanError> {
+        let teleporters = Arc::make_mut(&mut self.grid).pair_teleporters(&self.obstacles);
+        teleporters
+            .into_iter()
+            .filter(|(_, indexes)| indexes.len() != 2)
+            .map(|(id, indexes)| {
+                // Point to the only teleporter or to the first extra one
+                let position = self.obstacles[indexes[indexes.len().min(3) - 1]].position;
+                BombermanError::UnpairedTeleporter {
+                    id,
+                    count: indexes.len() as u32,
+                    location: Location::new(position, &ObstacleType::Teleporter(id).to_string()),
+                }
+            })
+            .collect()
+    }
+
+    // Return the current turn of the game, 0 before the first explosion
+    pub fn turn(&self) -> u32 {
+        self.turn
+    }
+
+    // Return the position of every bomb that has not been hit yet
+    pub fn bomb_positions(&self) -> Vec<Point> {
+        self.bombs
+            .iter()
+            .filter(|bomb| bomb.is_idle())
+            .map(|bomb| bomb.get_position())
+            .collect()
+    }
+
+    // Return the number of enemies that are not dead
+    pub fn enemies_alive(&self) -> u32 {
+        self.enemies.iter().filter(|enemy| enemy.is_alive()).count() as u32
+    }
+
+    // Return the squares each idle bomb would reach if it exploded now, the game is not changed
+    pub(crate) fn explosion_reach(&self) -> Vec<(Point, Vec<Point>)> {
+        self.bombs
+            .iter()
+            .filter(|bomb| bomb.is_idle())
+            .map(|bomb| {
+                let reach = bomb.clone().explode(&self.grid, &self.obstacles);
+                (bomb.get_position(), reach)
+            })
+            .collect()
+    }
+
+    // Return the position of every enemy that is alive
+    pub(crate) fn enemy_positions(&self) -> Vec<Point> {
+        self.enemies
+            .iter()
+            .filter(|enemy| enemy.is_alive())
+            .map(|enemy| enemy.get_position())
+            .collect()
+    }
+
+    pub(crate) fn obstacles(&self) -> &[Obstacle] {
+        &self.obstacles
+    }
+
+    pub(crate) fn grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    // Return the number of columns of the maze
+    pub fn width(&self) -> u32 {
+        self.grid.width()
+    }
+
+    // Return the number of lines of the maze
+    pub fn height(&self) -> u32 {
+        self.grid.height()
+    }
+
+    // Add a square to the game and index it in the grid
     fn add_square(&mut self, square: &str, point: Point) -> Result<(), BombermanError> {
         match square.get(..1) {
             Some(enemy::ENEMY) => {
-                let enemy = match Enemy::new(square, point) {
-                    Ok(enemy) => enemy,
-                    Err(e) => return Err(e),
-                };
+                let enemy = Enemy::new(square, point)?;
+                Arc::make_mut(&mut self.grid).insert(point, Occupant::Enemy(self.enemies.len()));
                 self.enemies.push(enemy);
             }
             Some(bomb_type::NORMAL_BOMB | bomb_type::PENETRATING_BOMB) => {
-                let bomb = match Bomb::new(square, point) {
-                    Ok(bomb) => bomb,
-                    Err(e) => return Err(e),
-                };
+                let bomb = Bomb::new(square, point)?;
+                Arc::make_mut(&mut self.grid).insert(point, Occupant::Bomb(self.bombs.len()));
                 self.bombs.push(bomb);
             }
             Some(x) if ObstacleType::is_obstacle(x) => {
-                let obstacle = match Obstacle::new(square, point) {
-                    Ok(obstacle) => obstacle,
-                    Err(e) => return Err(e),
-                };
-                self.obstacles.push(obstacle);
+                let obstacle = Obstacle::new(square, point)?;
+                Arc::make_mut(&mut self.grid)
+                    .insert(point, Occupant::Obstacle(self.obstacles.len()));
+                Arc::make_mut(&mut self.obstacles).push(obstacle);
             }
             Some("_") => (),
             _ => {
-                return Err(BombermanError::InvalidSquare(format!(
-                    "The square {square} at position {point} is invalid"
-                )))
+                return Err(BombermanError::invalid_square(
+                    point,
+                    square,
+                    &format!("The square {square} at position {point} is invalid"),
+                ))
             }
         }
         Ok(())
     }
 
     // Set game for next turn
-    //  - Reset enemies state
+    //  - Queue each bomb activated this turn to explode in its own turn
+    //  - Reset the state of the enemies and obstacles hit this turn
     fn next_turn(&mut self) {
-        self.enemies
-            .iter_mut()
-            .for_each(Enemy::reset_state);
+        let activated = self.schedule_activated_bombs();
+        self.turns
+            .extend(activated.into_iter().map(|index| vec![index]));
+        for index in self.hit_enemies.drain(..) {
+            self.enemies[index].reset_state();
+        }
+        self.hit_obstacles.clear();
+    }
+
+    // Schedule the bombs activated this turn that have a fuse to explode when it expires
+    // Returns the bombs without a fuse, in the order they were activated
+    fn schedule_activated_bombs(&mut self) -> Vec<usize> {
+        let mut without_fuse = Vec::new();
+        for index in self.activated_bombs.drain(..) {
+            match self.bombs[index].fuse() {
+                Some(fuse) => self
+                    .fuses
+                    .entry(self.turn.saturating_add(fuse))
+                    .or_default()
+                    .push(index),
+                None => without_fuse.push(index),
+            }
+        }
+        without_fuse
     }
 
     fn get_hittable_in_position(&mut self, position: Point) -> Option<&mut dyn CanBeHit> {
-        let mut hittable: Option<&mut dyn CanBeHit> = None;
-        if let Some(enemy) = self
-            .enemies
-            .iter_mut()
-            .find(|enemy| enemy.in_position(position))
-        {
-            hittable = Some(enemy);
+        match self.grid.get(position)? {
+            Occupant::Enemy(index) => Some(&mut self.enemies[index]),
+            Occupant::Bomb(index) => Some(&mut self.bombs[index]),
+            // Like enemies, a breakable block is hit at most once in each turn
+            Occupant::Obstacle(index)
+                if self.obstacles[index].is_breakable() && !self.hit_obstacles.contains(&index) =>
+            {
+                Some(&mut Arc::make_mut(&mut self.obstacles)[index])
+            }
+            Occupant::Obstacle(_) => None,
         }
-        if let Some(bomb) = self
-            .bombs
-            .iter_mut()
-            .find(|bomb| bomb.in_position(position))
-        {
-            hittable = Some(bomb);
+    }
+
+    // Hit the object in the position, if any
+    // Activated bombs are queued to explode and hit enemies are reset in the next turn
+    // Returns the event produced by the hit
+    fn hit_position(&mut self, position: Point) -> Option<BombermanEvent> {
+        let event = self.get_hittable_in_position(position)?.hit()?;
+        match self.grid.get(position) {
+            Some(Occupant::Bomb(index)) => self.activated_bombs.push(index),
+            Some(Occupant::Enemy(index)) => self.hit_enemies.push(index),
+            Some(Occupant::Obstacle(index)) => self.hit_obstacles.push(index),
+            _ => (),
         }
-        hittable
+        Some(event)
     }
 
     // Plays the game with the given starting bomb
     // Returns the string of the maze after the game or an error
     pub fn play(&mut self, start_bomb: Point) -> Result<String, BombermanError> {
-        match self
-            .bombs
-            .iter_mut()
-            .find(|bomb| bomb.in_position(start_bomb))
-        {
-            Some(bomb) => bomb.hit(),
-            None => {
-                return Err(BombermanError::NoBombInStartingPosition(format!(
-                    "No bomb in starting position: {start_bomb}",
-                )))
-            }
+        let (board, _) = self.play_with_events(start_bomb)?;
+        Ok(board)
+    }
+
+    // Plays the game with the given starting bomb
+    // Returns the string of the maze after the game and the ordered list of events
+    // that happened during the chain reaction, or an error
+    pub fn play_with_events(
+        &mut self,
+        start_bomb: Point,
+    ) -> Result<(String, Vec<BombermanEvent>), BombermanError> {
+        self.play_many_with_events(&[start_bomb])
+            .map_err(first_error)
+    }
+
+    // Plays the game with all the given starting bombs exploding in the same turn
+    // Returns the string of the maze after the game or an error for each starting
+    // position without a bomb
+    pub fn play_many(&mut self, start_bombs: &[Point]) -> Result<String, Vec<BombermanError>> {
+        let (board, _) = self.play_many_with_events(start_bombs)?;
+        Ok(board)
+    }
+
+    // Plays the game with all the given starting bombs exploding in the same turn
+    // Returns the string of the maze after the game and the ordered list of events
+    // or an error for each starting position without a bomb
+    pub fn play_many_with_events(
+        &mut self,
+        start_bombs: &[Point],
+    ) -> Result<(String, Vec<BombermanEvent>), Vec<BombermanError>> {
+        let mut events = self.activate_start_bombs(start_bombs)?;
+        while let Some((_, turn_events)) = self.explode_next_turn() {
+            events.extend(turn_events);
+        }
+        Ok((self.to_string(), events))
+    }
+
+    // Plays a copy of the game with the given starting bomb, the game is not changed
+    // Returns the outcome of the chain reaction or an error
+    pub fn simulate(&self, start_bomb: Point) -> Result<Outcome, BombermanError> {
+        let mut game = self.clone();
+        let (board, events) = game.play_with_events(start_bomb)?;
+        Ok(Outcome::new(
+            board,
+            events,
+            game.turn(),
+            game.enemies_alive(),
+        ))
+    }
+
+    // Activate the bombs in the starting positions so they all explode in the next turn
+    // Nothing is activated if any of the positions has no bomb
+    // Returns the activation events or an error for each position without a bomb
+    pub(crate) fn activate_start_bombs(
+        &mut self,
+        start_bombs: &[Point],
+    ) -> Result<Vec<BombermanEvent>, Vec<BombermanError>> {
+        let errors: Vec<BombermanError> = start_bombs
+            .iter()
+            .filter(|start| !matches!(self.grid.get(**start), Some(Occupant::Bomb(_))))
+            .map(|start| BombermanError::NoBombInStartingPosition(*start))
+            .collect();
+        if !errors.is_empty() {
+            return Err(errors);
         }
 
-        while let Some(bomb) = self.bombs.iter_mut().find(|bomb| bomb.is_active()) {
-            let afected_positions = bomb.explode(self.size, &self.obstacles);
+        let events = start_bombs
+            .iter()
+            .filter_map(|start| self.hit_position(*start))
+            .collect();
+        let start_turn = self.schedule_activated_bombs();
+        if !start_turn.is_empty() {
+            self.turns.push_back(start_turn);
+        }
+        Ok(events)
+    }
+
+    // Return true if there is a bomb waiting to explode
+    pub(crate) fn has_active_bombs(&self) -> bool {
+        !self.turns.is_empty() || !self.fuses.is_empty()
+    }
+
+    // Explode the bombs of the next turn, hit everything in their range and set the game
+    // for the next turn
+    // The starting bombs explode together in the first turn, then every other bomb
+    // explodes in its own turn in the same order they were activated
+    // Bombs with a fuse hit in turn t explode in turn t + fuse, together with every other
+    // bomb whose fuse expires in that turn. Turns where nothing explodes are skipped
+    // An enemy in the range of several bombs of the same turn is only hit once
+    // Returns the positions of the bombs and the events of the turn, or None if no bomb is active
+    pub(crate) fn explode_next_turn(&mut self) -> Option<(Vec<Point>, Vec<BombermanEvent>)> {
+        self.turn = match (self.turns.front(), self.fuses.keys().next()) {
+            (Some(_), _) => self.turn + 1,
+            (None, Some(turn)) => *turn,
+            (None, None) => return None,
+        };
+        let mut turn = self.turns.pop_front().unwrap_or_default();
+        turn.extend(self.fuses.remove(&self.turn).unwrap_or_default());
+
+        let mut positions = Vec::new();
+        let mut events = Vec::new();
+        for index in turn {
+            let bomb = &mut self.bombs[index];
+            let position = bomb.get_position();
+            let mut explosion_events = Vec::new();
+            let mut afected_positions =
+                bomb.explode_with_events(&self.grid, &self.obstacles, &mut explosion_events);
+            afected_positions.sort();
+
+            events.push(BombermanEvent::BombExploded {
+                position,
+                affected: afected_positions.clone(),
+            });
+            events.extend(explosion_events);
             for position in afected_positions {
-                if let Some(hittable) = self.get_hittable_in_position(position) {
-                    hittable.hit();
-                }
+                events.extend(self.hit_position(position));
             }
-            self.next_turn();
+            positions.push(position);
         }
-
-        Ok(self.to_string())
+        self.next_turn();
+        Some((positions, events))
     }
 
     // Return all the displayable objects
@@ -151,7 +504,7 @@ impl Bomberman {
 
     // Convert game to matrix
     fn to_matrix(&self) -> Vec<Vec<String>> {
-        let mut matrix = vec![vec!["_".to_string(); self.size as usize]; self.size as usize];
+        let mut matrix = vec![vec!["_".to_string(); self.width() as usize]; self.height() as usize];
         let displayable = self.get_all_displayable();
         for displayable in displayable {
             let position = displayable.get_position();
@@ -161,6 +514,16 @@ impl Bomberman {
     }
 }
 
+// Return the first error, parse_all never fails without errors
+fn first_error(errors: Vec<BombermanError>) -> BombermanError {
+    match errors.into_iter().next() {
+        Some(error) => error,
+        None => {
+            BombermanError::invalid_square(Point::new(0, 0), "", "The maze could not be parsed")
+        }
+    }
+}
+
 impl Display for Bomberman {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         let mut display = String::new();
@@ -186,6 +549,36 @@ mod test {
         assert_eq!(result, board);
     }
 
+    #[test]
+    fn test_enemy_is_hit_after_bouncing_between_mirrors() {
+        let input = "_ / _ \\\n_ _ _ _\nB7 / _ F1\n";
+        let result = "_ / _ \\\n_ _ _ _\n_ / _ _\n";
+        let mut game = Bomberman::new(input).unwrap();
+        let board = game.play(Point::new(0, 2)).unwrap();
+        assert_eq!(result, board);
+    }
+
+    #[test]
+    fn test_enemies_are_hit_by_both_arms_of_splitter() {
+        let input = "_ _ F1\nB4 _ +\n_ _ F2\n";
+        let result = "_ _ _\n_ _ +\n_ _ F1\n";
+        let mut game = Bomberman::new(input).unwrap();
+        let board = game.play(Point::new(0, 1)).unwrap();
+        assert_eq!(result, board);
+    }
+
+    #[test]
+    fn test_bomb_with_largest_distance_in_redirection_loop() {
+        let input = "DR F3 DL\nS4294967295 _ _\n";
+        let result = "DR F2 DL\n_ _ _\n";
+        let mut game = Bomberman::new(input).unwrap();
+        let (board, events) = game.play_with_events(Point::new(0, 1)).unwrap();
+        assert_eq!(result, board);
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, BombermanEvent::ExplosionLooped { .. })));
+    }
+
     #[test]
     fn test_enemy_is_not_hit_twice_by_same_bomb() {
         let input = "B5 F2 DL\n _ _ _\n_ _ _\n";
@@ -204,21 +597,494 @@ mod test {
         assert_eq!(result, board);
     }
 
+    #[test]
+    fn test_play_with_events_logs_chain_reaction() {
+        let input = "B2 F2 W\nB1 _ _\n_ _ _\n";
+        let mut game = Bomberman::new(input).unwrap();
+        let (_, events) = game.play_with_events(Point::new(0, 0)).unwrap();
+        let expected = vec![
+            BombermanEvent::BombActivated(Point::new(0, 0)),
+            BombermanEvent::BombExploded {
+                position: Point::new(0, 0),
+                affected: vec![
+                    Point::new(0, 0),
+                    Point::new(0, 1),
+                    Point::new(0, 2),
+                    Point::new(1, 0),
+                ],
+            },
+            BombermanEvent::ExplosionStopped {
+                position: Point::new(2, 0),
+                obstacle: ObstacleType::Wall,
+            },
+            BombermanEvent::BombActivated(Point::new(0, 1)),
+            BombermanEvent::EnemyHit {
+                position: Point::new(1, 0),
+                health: 1,
+            },
+            BombermanEvent::BombExploded {
+                position: Point::new(0, 1),
+                affected: vec![
+                    Point::new(0, 0),
+                    Point::new(0, 1),
+                    Point::new(0, 2),
+                    Point::new(1, 1),
+                ],
+            },
+        ];
+        assert_eq!(expected, events);
+    }
+
+    #[test]
+    fn test_start_bombs_explode_in_the_same_turn() {
+        let input = "B2 _ F2 _ B2\n_ _ _ _ _\n";
+        let result = "_ _ F1 _ _\n_ _ _ _ _\n";
+        let mut game = Bomberman::new(input).unwrap();
+        let board = game
+            .play_many(&[Point::new(0, 0), Point::new(4, 0)])
+            .unwrap();
+        assert_eq!(result, board);
+    }
+
+    #[test]
+    fn test_bomb_hit_by_start_bombs_explodes_in_its_own_turn() {
+        let input = "B2 B2 F2 _ B2\n_ _ _ _ _\n";
+        let result = "_ _ _ _ _\n_ _ _ _ _\n";
+        let mut game = Bomberman::new(input).unwrap();
+        let board = game
+            .play_many(&[Point::new(0, 0), Point::new(4, 0)])
+            .unwrap();
+        assert_eq!(result, board);
+    }
+
+    #[test]
+    fn test_each_missing_start_bomb_is_an_error() {
+        let input = "B2 _ F2\n_ _ _\n_ _ B1\n";
+        let mut game = Bomberman::new(input).unwrap();
+        let errors = game
+            .play_many(&[Point::new(1, 0), Point::new(0, 0), Point::new(2, 0)])
+            .unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                BombermanError::NoBombInStartingPosition(Point::new(1, 0)),
+                BombermanError::NoBombInStartingPosition(Point::new(2, 0)),
+            ]
+        );
+        assert_eq!(game.to_string(), input);
+    }
+
+    #[test]
+    fn test_bomb_without_fuse_behaves_as_before() {
+        let input = "B2 _ B1\n_ _ _\nB1 _ _\n";
+        let mut game = Bomberman::new(input).unwrap();
+        let board = game.play(Point::new(0, 0)).unwrap();
+        assert_eq!(board, "_ _ _\n_ _ _\n_ _ _\n");
+        assert_eq!(game.turn(), 3);
+    }
+
+    #[test]
+    fn test_fused_bomb_explodes_after_its_fuse() {
+        let input = "B1 B2:3 _\n_ B1 _\n_ _ _\n";
+        let mut game = Bomberman::new(input).unwrap();
+        let mut turns = Vec::new();
+        game.activate_start_bombs(&[Point::new(0, 0)]).unwrap();
+        while let Some((bombs, _)) = game.explode_next_turn() {
+            turns.push((game.turn(), bombs));
+        }
+        assert_eq!(
+            turns,
+            vec![
+                (1, vec![Point::new(0, 0)]),
+                (4, vec![Point::new(1, 0)]),
+                (5, vec![Point::new(1, 1)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fused_start_bomb_waits_for_its_fuse() {
+        let input = "B1:2 _\n_ _\n";
+        let mut game = Bomberman::new(input).unwrap();
+        let board = game.play(Point::new(0, 0)).unwrap();
+        assert_eq!(board, "_ _\n_ _\n");
+        assert_eq!(game.turn(), 2);
+    }
+
+    #[test]
+    fn test_fuses_expiring_in_the_same_turn_explode_together() {
+        let input = "B1 B1:2 F2\nB1:1 _ _\nB1 _ _\n";
+        let mut game = Bomberman::new(input).unwrap();
+        game.activate_start_bombs(&[Point::new(0, 0)]).unwrap();
+        let mut turns = Vec::new();
+        while let Some((bombs, _)) = game.explode_next_turn() {
+            turns.push((game.turn(), bombs));
+        }
+        assert_eq!(
+            turns,
+            vec![
+                (1, vec![Point::new(0, 0)]),
+                (2, vec![Point::new(0, 1)]),
+                (3, vec![Point::new(0, 2), Point::new(1, 0)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_simulate_does_not_change_the_game() {
+        let input = "B2 F1 _\n_ _ _\nB1 _ F2\n";
+        let game = Bomberman::new(input).unwrap();
+        let first = game.simulate(Point::new(0, 0)).unwrap();
+        let second = game.simulate(Point::new(0, 2)).unwrap();
+        assert_eq!(first.board, "_ _ _\n_ _ _\n_ _ F2\n");
+        assert_eq!((first.kills, first.remaining_enemies), (1, 1));
+        assert_eq!(first.turns, 2);
+        assert_eq!(second.board, "B2 F1 _\n_ _ _\n_ _ F2\n");
+        assert_eq!(second.turns, 1);
+        assert_eq!(game.to_string(), input);
+    }
+
+    #[test]
+    fn test_json_round_trip_keeps_the_board() {
+        let input = "B2:3 R F3 _\n_ W DU S1\n";
+        let game = Bomberman::new(input).unwrap();
+        let copy = Bomberman::from_json(&game.to_json()).unwrap();
+        assert_eq!(copy.to_string(), input);
+        assert_eq!(copy.to_json(), game.to_json());
+    }
+
+    #[test]
+    fn test_json_after_play_leaves_out_exploded_bombs_and_dead_enemies() {
+        let mut game = Bomberman::new("B2 F1 F2\n").unwrap();
+        let board = game.play(Point::new(0, 0)).unwrap();
+        let copy = Bomberman::from_json(&game.to_json()).unwrap();
+        assert_eq!(copy.to_string(), board);
+        assert_eq!(copy.enemies_alive(), 1);
+    }
+
+    #[test]
+    fn test_json_square_out_of_the_maze_is_error() {
+        let json = r#"{"size": {"width": 1, "height": 1},
+            "enemies": [{"health": 1, "position": {"x": 1, "y": 0}}]}"#;
+        let error = Bomberman::from_json(json).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "InvalidSquare: The square F1 at position (1, 0) is out of the maze"
+        );
+    }
+
+    #[test]
+    fn test_json_squares_in_the_same_position_is_error() {
+        let json = r#"{"size": {"width": 1, "height": 1},
+            "bombs": [{"type": "Normal", "range": 1, "position": {"x": 0, "y": 0}}],
+            "enemies": [{"health": 1, "position": {"x": 0, "y": 0}}]}"#;
+        let error = Bomberman::from_json(json).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "InvalidSquare: The square F1 at position (0, 0) is already taken"
+        );
+    }
+
+    #[test]
+    fn test_svg_leaves_out_exploded_bombs() {
+        let mut game = Bomberman::new("B1 B1\n_ R\n").unwrap();
+        assert_eq!(game.to_svg(&[]).matches("class=\"bomb\"").count(), 2);
+        let (_, events) = game.play_with_events(Point::new(0, 0)).unwrap();
+        let svg = game.to_svg(&svg::blast_squares(&events));
+        assert_eq!(svg.matches("class=\"bomb\"").count(), 0);
+        assert_eq!(svg.matches("class=\"rock\"").count(), 1);
+        assert_eq!(svg.matches("class=\"blast\"").count(), 3);
+    }
+
+    #[test]
+    fn test_clones_share_the_obstacles() {
+        let game = Bomberman::new("B1 W\n_ R\n").unwrap();
+        let copy = game.clone();
+        assert!(Arc::ptr_eq(&game.obstacles, &copy.obstacles));
+        assert!(Arc::ptr_eq(&game.grid, &copy.grid));
+    }
+
+    #[test]
+    fn test_normal_explosion_breaks_block_and_stops() {
+        let mut game = Bomberman::new("B3 X F1\n").unwrap();
+        let (board, events) = game.play_with_events(Point::new(0, 0)).unwrap();
+        assert_eq!(board, "_ _ F1\n");
+        assert!(events.contains(&BombermanEvent::ObstacleBroken(Point::new(1, 0))));
+    }
+
+    #[test]
+    fn test_breakable_block_with_hits_needs_several_explosions() {
+        let mut game = Bomberman::new("B1 X2 _\n").unwrap();
+        let board = game.play(Point::new(0, 0)).unwrap();
+        assert_eq!(board, "_ X _\n");
+        let mut game = Bomberman::new("B1 B1 _\nB1 X2 _\n").unwrap();
+        let board = game.play(Point::new(0, 1)).unwrap();
+        assert_eq!(board, "_ _ _\n_ _ _\n");
+    }
+
+    #[test]
+    fn test_penetrating_explosion_hits_block_and_passes() {
+        let mut game = Bomberman::new("S3 X2 F1\n").unwrap();
+        let board = game.play(Point::new(0, 0)).unwrap();
+        assert_eq!(board, "_ X _\n");
+    }
+
+    #[test]
+    fn test_block_is_hit_once_per_turn() {
+        let mut game = Bomberman::new("B1 X2 B1\n").unwrap();
+        let board = game
+            .play_many(&[Point::new(0, 0), Point::new(2, 0)])
+            .unwrap();
+        assert_eq!(board, "_ X _\n");
+    }
+
+    #[test]
+    fn test_simulate_does_not_break_blocks_of_the_game() {
+        let game = Bomberman::new("B2 X\n").unwrap();
+        let outcome = game.simulate(Point::new(0, 0)).unwrap();
+        assert_eq!(outcome.board, "_ _\n");
+        assert_eq!(game.to_string(), "B2 X\n");
+    }
+
+    #[test]
+    fn test_json_round_trip_keeps_breakable_hits() {
+        let game = Bomberman::new("B1 X3 X\n").unwrap();
+        let copy = Bomberman::from_json(&game.to_json()).unwrap();
+        assert_eq!(copy.to_string(), "B1 X3 X\n");
+    }
+
     #[test]
     fn board_not_square_returns_error() {
         let input = "B5 B2\n_ _ _\n";
-        let result = BombermanError::MazeNotSquare(
-            "Maze has 2 lines and 3 columns, it should be equal".to_string(),
+        let result = BombermanError::MazeNotSquare {
+            lines: 2,
+            columns: 3,
+            location: Location {
+                position: Point::new(2, 1),
+                token: "_".to_string(),
+                span: Some(Span { start: 10, end: 11 }),
+            },
+        };
+        let game = Bomberman::new_strict(input);
+        let error = game.unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "MazeNotSquare: Maze has 2 lines and 3 columns, it should be equal"
+        );
+        assert_eq!(result, error);
+    }
+
+    #[test]
+    fn rectangular_board_not_square_returns_error_in_strict_mode() {
+        let input = "B5 B2 _\n_ _ _\n";
+        let game = Bomberman::new_strict(input);
+        assert_eq!(
+            game.unwrap_err().to_string(),
+            "MazeNotSquare: Maze has 2 lines and 3 columns, it should be equal"
         );
+    }
+
+    #[test]
+    fn board_with_different_row_lengths_returns_error() {
+        let input = "B5 B2\n_ _ _\n";
+        let result = BombermanError::MazeNotRectangular {
+            columns: 3,
+            expected: 2,
+            location: Location {
+                position: Point::new(2, 1),
+                token: "_".to_string(),
+                span: Some(Span { start: 10, end: 11 }),
+            },
+        };
         let game = Bomberman::new(input);
-        assert_eq!(result, game.unwrap_err());
+        let error = game.unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "MazeNotRectangular: Row 2 has 3 columns, it should have 2 like the first row"
+        );
+        assert_eq!(result, error);
+    }
+
+    #[test]
+    fn parse_all_collects_every_invalid_square() {
+        let input = "Y B2 _\n_ F9 _\nB0 _ Q\n";
+        let errors = Bomberman::parse_all(input, false).unwrap_err();
+        let locations: Vec<(Point, &str, Span)> = errors
+            .iter()
+            .filter_map(BombermanError::location)
+            .map(|l| (l.position, l.token.as_str(), l.span.unwrap()))
+            .collect();
+        assert_eq!(
+            locations,
+            vec![
+                (Point::new(0, 0), "Y", Span { start: 0, end: 1 }),
+                (Point::new(1, 1), "F9", Span { start: 9, end: 11 }),
+                (Point::new(0, 2), "B0", Span { start: 14, end: 16 }),
+                (Point::new(2, 2), "Q", Span { start: 19, end: 20 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_all_collects_row_length_problems() {
+        let input = "_ _ _\n_ _\n_ _ _ B1\n";
+        let errors = Bomberman::parse_all(input, false).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                BombermanError::MazeNotRectangular {
+                    columns: 2,
+                    expected: 3,
+                    location: Location {
+                        position: Point::new(2, 1),
+                        token: "".to_string(),
+                        span: Some(Span { start: 9, end: 9 }),
+                    },
+                },
+                BombermanError::MazeNotRectangular {
+                    columns: 4,
+                    expected: 3,
+                    location: Location {
+                        position: Point::new(3, 2),
+                        token: "B1".to_string(),
+                        span: Some(Span { start: 16, end: 18 }),
+                    },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_all_reports_row_length_after_the_squares_of_the_row() {
+        let input = "B1 _\nQ _ _\n";
+        let errors = Bomberman::parse_all(input, false).unwrap_err();
+        let errors: Vec<String> = errors.iter().map(|error| error.to_string()).collect();
+        assert_eq!(
+            errors,
+            vec![
+                "InvalidSquare: The square Q at position (0, 1) is invalid".to_string(),
+                "MazeNotRectangular: Row 2 has 3 columns, it should have 2 like the first row"
+                    .to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_errors_render_the_offending_line() {
+        let input = "B1 _\nF1 DX\n";
+        let errors = Bomberman::parse_all(input, false).unwrap_err();
+        let expected = "InvalidSquare: invalid obstacle DX at (1, 1)\n --> line 2, column 4, maze position (1, 1)\n  |\n2 | F1 DX\n  |    ^^\n";
+        assert_eq!(errors[0].render(input), expected);
+    }
+
+    #[test]
+    fn parse_all_reports_unpaired_teleporters() {
+        let input = "B1 T1 T2\nT2 _ T2\n";
+        let errors = Bomberman::parse_all(input, false).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                BombermanError::UnpairedTeleporter {
+                    id: 1,
+                    count: 1,
+                    location: Location {
+                        position: Point::new(1, 0),
+                        token: "T1".to_string(),
+                        span: Some(Span { start: 3, end: 5 }),
+                    },
+                },
+                BombermanError::UnpairedTeleporter {
+                    id: 2,
+                    count: 3,
+                    location: Location {
+                        position: Point::new(2, 1),
+                        token: "T2".to_string(),
+                        span: Some(Span { start: 14, end: 16 }),
+                    },
+                },
+            ]
+        );
+        assert_eq!(
+            errors[0].to_string(),
+            "UnpairedTeleporter: Teleporter T1 should appear exactly twice, found 1"
+        );
+    }
+
+    #[test]
+    fn test_json_unpaired_teleporter_is_error() {
+        let json = r#"{"size": {"width": 2, "height": 1},
+            "obstacles": [{"type": "Teleporter", "id": 3, "position": {"x": 1, "y": 0}}]}"#;
+        let error = Bomberman::from_json(json).unwrap_err();
+        assert!(matches!(
+            error,
+            BombermanError::UnpairedTeleporter {
+                id: 3,
+                count: 1,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_enemy_is_hit_through_teleporters() {
+        let input = "B3 _ T4 W\nW W W W\nF1 _ T4 F2\n";
+        let result = "_ _ T4 W\nW W W W\nF1 _ T4 F1\n";
+        let mut game = Bomberman::new(input).unwrap();
+        let (board, events) = game.play_with_events(Point::new(0, 0)).unwrap();
+        assert_eq!(result, board);
+        assert!(events.contains(&BombermanEvent::ExplosionTeleported {
+            from: Point::new(2, 0),
+            to: Point::new(2, 2),
+        }));
+    }
+
+    #[test]
+    fn test_teleporters_to_json_and_back() {
+        let game = Bomberman::new("B1 T9\nT9 _\n").unwrap();
+        let json = game.to_json();
+        assert!(json.contains("\"id\": 9"));
+        assert_eq!(
+            Bomberman::from_json(&json).unwrap().to_string(),
+            "B1 T9\nT9 _\n"
+        );
+    }
+
+    #[test]
+    fn parse_all_returns_game_when_valid() {
+        let input = "B1 _\n_ F1\n";
+        let game = Bomberman::parse_all(input, true).unwrap();
+        assert_eq!(game.to_string(), input);
+    }
+
+    #[test]
+    fn test_play_in_wide_board() {
+        let input = "B3 _ _ F1 _\n_ _ _ _ _\n";
+        let result = "_ _ _ _ _\n_ _ _ _ _\n";
+        let mut game = Bomberman::new(input).unwrap();
+        assert_eq!((game.width(), game.height()), (5, 2));
+        let board = game.play(Point::new(0, 0)).unwrap();
+        assert_eq!(result, board);
+    }
+
+    #[test]
+    fn test_play_in_tall_board() {
+        let input = "B3\n_\nF1\nB1\n";
+        let result = "_\n_\n_\n_\n";
+        let mut game = Bomberman::new(input).unwrap();
+        assert_eq!((game.width(), game.height()), (1, 4));
+        let board = game.play(Point::new(0, 0)).unwrap();
+        assert_eq!(result, board);
     }
 
     #[test]
     fn invalid_square_returns_error() {
-        let input = "X B2\n_ _ \n";
-        let result =
-            BombermanError::InvalidSquare("The square X at position (0, 0) is invalid".to_string());
+        let input = "Y B2\n_ _ \n";
+        let result = BombermanError::invalid_square(
+            Point::new(0, 0),
+            "Y",
+            "The square Y at position (0, 0) is invalid",
+        )
+        .with_span(Span { start: 0, end: 1 });
         let game = Bomberman::new(input);
         assert_eq!(result, game.unwrap_err());
     }
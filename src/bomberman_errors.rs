@@ -1,20 +1,201 @@
+use crate::utils::point::Point;
 use std::fmt::Display;
 
-#[derive(Debug, PartialEq)]
+// Byte range of a token in the maze file, end excluded
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    // Return the span of a token that is a slice of the source string
+    // Only used by the parser, the token must be borrowed from the source
+    pub(crate) fn of(source: &str, token: &str) -> Span {
+        let start = token.as_ptr() as usize - source.as_ptr() as usize;
+        Span {
+            start,
+            end: start + token.len(),
+        }
+    }
+}
+
+// Where an error happened: the position in the maze, the raw token and,
+// if known, the span of the token in the maze file
+#[derive(Debug, PartialEq, Clone)]
+pub struct Location {
+    pub position: Point,
+    pub token: String,
+    pub span: Option<Span>,
+}
+
+impl Location {
+    pub fn new(position: Point, token: &str) -> Location {
+        Location {
+            position,
+            token: token.to_string(),
+            span: None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
 pub enum BombermanError {
-    MazeNotSquare(String),
-    InvalidSquare(String),
-    NoBombInStartingPosition(String),
+    MazeNotSquare {
+        lines: u32,
+        columns: u32,
+        location: Location,
+    },
+    MazeNotRectangular {
+        columns: u32,
+        expected: u32,
+        location: Location,
+    },
+    InvalidSquare {
+        reason: String,
+        location: Location,
+    },
+    NoBombInStartingPosition(Point),
+    InvalidJson(String),
+    // The location is the only teleporter with the id, or the first one after its pair
+    UnpairedTeleporter {
+        id: u32,
+        count: u32,
+        location: Location,
+    },
+}
+
+impl BombermanError {
+    // Create an invalid square error for the token in the position
+    pub fn invalid_square(position: Point, token: &str, reason: &str) -> BombermanError {
+        BombermanError::InvalidSquare {
+            reason: reason.to_string(),
+            location: Location::new(position, token),
+        }
+    }
+
+    // Return where the error happened, if it happened in a square of the maze
+    pub fn location(&self) -> Option<&Location> {
+        match self {
+            BombermanError::MazeNotSquare { location, .. }
+            | BombermanError::MazeNotRectangular { location, .. }
+            | BombermanError::InvalidSquare { location, .. }
+            | BombermanError::UnpairedTeleporter { location, .. } => Some(location),
+            BombermanError::NoBombInStartingPosition(_) | BombermanError::InvalidJson(_) => None,
+        }
+    }
+
+    // Return the error with the span of its token in the maze file
+    pub fn with_span(mut self, span: Span) -> BombermanError {
+        match &mut self {
+            BombermanError::MazeNotSquare { location, .. }
+            | BombermanError::MazeNotRectangular { location, .. }
+            | BombermanError::InvalidSquare { location, .. }
+            | BombermanError::UnpairedTeleporter { location, .. } => location.span = Some(span),
+            BombermanError::NoBombInStartingPosition(_) | BombermanError::InvalidJson(_) => (),
+        }
+        self
+    }
+
+    // Render the error like a compiler error, showing the line of the maze file
+    // with a caret under the offending token
+    // Errors without a span are rendered as their Display
+    pub fn render(&self, source: &str) -> String {
+        let Some(location) = self.location() else {
+            return self.to_string();
+        };
+        let Some(span) = location.span.filter(|span| span.end <= source.len()) else {
+            return format!("{self}\n --> maze position {}\n", location.position);
+        };
+
+        let line_start = source[..span.start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[span.start..]
+            .find('\n')
+            .map_or(source.len(), |i| span.start + i);
+        let line = source[line_start..line_end].trim_end_matches('\r');
+        let line_number = source[..line_start].matches('\n').count() + 1;
+        let column = source[line_start..span.start].chars().count();
+        let carets = source[span.start..span.end].chars().count().max(1);
+
+        let gutter = " ".repeat(line_number.to_string().len());
+        format!(
+            "{self}\n{gutter}--> line {line_number}, column {}, maze position {}\n{gutter} |\n{line_number} | {line}\n{gutter} | {}{}\n",
+            column + 1,
+            location.position,
+            " ".repeat(column),
+            "^".repeat(carets)
+        )
+    }
 }
 
 impl Display for BombermanError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
-            BombermanError::MazeNotSquare(e) => write!(f, "MazeNotSquare: {e}"),
-            BombermanError::InvalidSquare(e) => write!(f, "InvalidSquare: {e}"),
-            BombermanError::NoBombInStartingPosition(e) => {
-                write!(f, "NoBombInStartingPosition: {e}")
+            BombermanError::MazeNotSquare { lines, columns, .. } => write!(
+                f,
+                "MazeNotSquare: Maze has {lines} lines and {columns} columns, it should be equal"
+            ),
+            BombermanError::MazeNotRectangular {
+                columns,
+                expected,
+                location,
+            } => write!(
+                f,
+                "MazeNotRectangular: Row {} has {columns} columns, it should have {expected} like the first row",
+                // Rows are numbered from 1, like the lines of render
+                location.position.y + 1
+            ),
+            BombermanError::InvalidSquare { reason, .. } => write!(f, "InvalidSquare: {reason}"),
+            BombermanError::NoBombInStartingPosition(position) => {
+                write!(
+                    f,
+                    "NoBombInStartingPosition: No bomb in starting position: {position}"
+                )
             }
+            BombermanError::InvalidJson(reason) => write!(f, "InvalidJson: {reason}"),
+            BombermanError::UnpairedTeleporter { id, count, .. } => write!(
+                f,
+                "UnpairedTeleporter: Teleporter T{id} should appear exactly twice, found {count}"
+            ),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_span_of_token() {
+        let source = "B1 _\nX F1\n";
+        let token = &source[5..6];
+        assert_eq!(Span::of(source, token), Span { start: 5, end: 6 });
+    }
+
+    #[test]
+    fn test_render_shows_caret_under_token() {
+        let source = "B1 _ _\n_ F9 _\n";
+        let error = BombermanError::invalid_square(Point::new(1, 1), "F9", "invalid enemy")
+            .with_span(Span { start: 9, end: 11 });
+        let expected = "InvalidSquare: invalid enemy\n --> line 2, column 3, maze position (1, 1)\n  |\n2 | _ F9 _\n  |   ^^\n";
+        assert_eq!(error.render(source), expected);
+    }
+
+    #[test]
+    fn test_render_without_span_shows_position() {
+        let error = BombermanError::invalid_square(Point::new(1, 1), "F9", "invalid enemy");
+        assert_eq!(
+            error.render(""),
+            "InvalidSquare: invalid enemy\n --> maze position (1, 1)\n"
+        );
+    }
+
+    #[test]
+    fn test_render_error_without_location() {
+        let error = BombermanError::NoBombInStartingPosition(Point::new(0, 0));
+        assert_eq!(
+            error.render("B1\n"),
+            "NoBombInStartingPosition: No bomb in starting position: (0, 0)"
+        );
+    }
+}
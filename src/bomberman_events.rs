@@ -0,0 +1,121 @@
+use crate::maze_placeable::obstacle_type::ObstacleType;
+use crate::utils::direction::Direction;
+use crate::utils::point::Point;
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum BombermanEvent {
+    BombActivated(Point),
+    BombExploded {
+        position: Point,
+        affected: Vec<Point>,
+    },
+    EnemyHit {
+        position: Point,
+        health: u32,
+    },
+    EnemyKilled(Point),
+    ExplosionStopped {
+        position: Point,
+        obstacle: ObstacleType,
+    },
+    ExplosionRedirected {
+        position: Point,
+        from: Direction,
+        to: Direction,
+    },
+    ExplosionSplit {
+        position: Point,
+        from: Direction,
+        to: [Direction; 2],
+    },
+    ExplosionTeleported {
+        from: Point,
+        to: Point,
+    },
+    // The explosion got back to the position going in the same direction and was stopped
+    ExplosionLooped {
+        position: Point,
+        direction: Direction,
+    },
+    ObstacleHit {
+        position: Point,
+        hits: u32,
+    },
+    ObstacleBroken(Point),
+}
+
+impl Display for BombermanEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            BombermanEvent::BombActivated(position) => write!(f, "BombActivated: {position}"),
+            BombermanEvent::BombExploded { position, affected } => {
+                let affected: Vec<String> = affected.iter().map(Point::to_string).collect();
+                write!(
+                    f,
+                    "BombExploded: {position} affected [{}]",
+                    affected.join(", ")
+                )
+            }
+            BombermanEvent::EnemyHit { position, health } => {
+                write!(f, "EnemyHit: {position} remaining health {health}")
+            }
+            BombermanEvent::EnemyKilled(position) => write!(f, "EnemyKilled: {position}"),
+            BombermanEvent::ExplosionStopped { position, obstacle } => {
+                write!(f, "ExplosionStopped: {position} by {obstacle}")
+            }
+            BombermanEvent::ExplosionRedirected { position, from, to } => {
+                write!(f, "ExplosionRedirected: {position} from {from:?} to {to:?}")
+            }
+            BombermanEvent::ExplosionSplit { position, from, to } => write!(
+                f,
+                "ExplosionSplit: {position} from {from:?} to {:?} and {:?}",
+                to[0], to[1]
+            ),
+            BombermanEvent::ExplosionTeleported { from, to } => {
+                write!(f, "ExplosionTeleported: {from} to {to}")
+            }
+            BombermanEvent::ExplosionLooped {
+                position,
+                direction,
+            } => write!(
+                f,
+                "ExplosionLooped: {position} going {direction:?} again, the explosion is stopped"
+            ),
+            BombermanEvent::ObstacleHit { position, hits } => {
+                write!(f, "ObstacleHit: {position} remaining hits {hits}")
+            }
+            BombermanEvent::ObstacleBroken(position) => write!(f, "ObstacleBroken: {position}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_display_bomb_exploded() {
+        let event = BombermanEvent::BombExploded {
+            position: Point::new(0, 0),
+            affected: vec![Point::new(0, 0), Point::new(1, 0)],
+        };
+        assert_eq!(
+            event.to_string(),
+            "BombExploded: (0, 0) affected [(0, 0), (1, 0)]"
+        );
+    }
+
+    #[test]
+    fn test_display_explosion_redirected() {
+        let event = BombermanEvent::ExplosionRedirected {
+            position: Point::new(2, 1),
+            from: Direction::Up,
+            to: Direction::Left,
+        };
+        assert_eq!(
+            event.to_string(),
+            "ExplosionRedirected: (2, 1) from Up to Left"
+        );
+    }
+}
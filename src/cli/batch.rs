@@ -0,0 +1,426 @@
+use crate::cli::flags::{Flags, INPUT, JOBS, MANIFEST, OUTPUT, STRICT};
+use crate::cli::io;
+use crate::cli::CliError;
+use ej_individual::bomberman::Bomberman;
+use ej_individual::input_errors::InputError;
+use ej_individual::maze_header::{parse_points, split_header};
+use ej_individual::utils::point::Point;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+// Name of the manifest read from the input directory when --manifest is not given
+pub const DEFAULT_MANIFEST: &str = "manifest.txt";
+
+// Result of playing one maze of the batch
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Ok,
+    NoStartPoint,
+    ParseError(String),
+    MissingStartBomb(String),
+    FileError(String),
+}
+
+impl Status {
+    fn name(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::NoStartPoint => "no start point",
+            Status::ParseError(_) => "parse error",
+            Status::MissingStartBomb(_) => "missing start bomb",
+            Status::FileError(_) => "file error",
+        }
+    }
+
+    fn detail(&self) -> &str {
+        match self {
+            Status::Ok | Status::NoStartPoint => "",
+            Status::ParseError(detail)
+            | Status::MissingStartBomb(detail)
+            | Status::FileError(detail) => detail,
+        }
+    }
+}
+
+// Play every maze of a directory or glob in parallel and print a summary of the results
+// The starting points of each maze come from the manifest or from its header
+// Return an error if any maze could not be played
+pub fn run(args: &[String]) -> Result<(), CliError> {
+    let flags = Flags::parse(args, &[INPUT, OUTPUT, MANIFEST, JOBS], &[STRICT])?;
+    let (dir, pattern) = split_glob(flags.required(INPUT)?);
+    let output_dir = io::format_out_path(flags.required(OUTPUT)?);
+    io::create_dir(&output_dir)?;
+    check_output_dir(&dir, &output_dir)?;
+    let manifest_path = match flags.value(MANIFEST) {
+        Some(path) => Some(PathBuf::from(path)),
+        None => Some(dir.join(DEFAULT_MANIFEST)).filter(|path| path.is_file()),
+    };
+    let manifest = match &manifest_path {
+        Some(path) => parse_manifest(&io::read_input(&path.to_string_lossy())?)?,
+        None => HashMap::new(),
+    };
+    let jobs = match flags.value(JOBS) {
+        Some(jobs) => match jobs.parse::<usize>() {
+            Ok(jobs) if jobs > 0 => jobs,
+            _ => {
+                return Err(InputError::InvalidInput(format!(
+                    "invalid number of jobs {jobs}, it should be a positive number"
+                ))
+                .into())
+            }
+        },
+        None => std::thread::available_parallelism().map_or(1, |jobs| jobs.get()),
+    };
+
+    let inputs = list_inputs(&dir, &pattern, manifest_path.as_deref())?;
+    let results = play_all(&inputs, &manifest, &output_dir, flags.has(STRICT), jobs);
+    print_summary(&inputs, &results);
+
+    let failed = results
+        .iter()
+        .filter(|status| **status != Status::Ok)
+        .count();
+    if failed > 0 {
+        return Err(CliError::Batch { failed });
+    }
+    Ok(())
+}
+
+// Return an error if the output directory is the input directory, the output of each
+// maze has its name so it would overwrite the maze
+// Both are compared once resolved, so different paths to the same directory are caught
+fn check_output_dir(input_dir: &Path, output_dir: &str) -> Result<(), CliError> {
+    let (Ok(input), Ok(output)) = (
+        input_dir.canonicalize(),
+        Path::new(output_dir).canonicalize(),
+    ) else {
+        return Ok(());
+    };
+    if input == output {
+        return Err(InputError::InvalidInput(format!(
+            "the output directory is the input directory {}, the mazes would be overwritten",
+            input_dir.display()
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+// Split the input in the directory to read and the pattern of the file names
+// A directory matches every file in it. Eg: mazes/*.txt -> (mazes, *.txt)
+fn split_glob(input: &str) -> (PathBuf, String) {
+    let path = Path::new(input);
+    let is_glob = input.contains('*') || input.contains('?');
+    match (is_glob, path.file_name()) {
+        (true, Some(name)) => {
+            let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty());
+            (
+                dir.map_or(PathBuf::from("."), Path::to_path_buf),
+                name.to_string_lossy().to_string(),
+            )
+        }
+        _ => (path.to_path_buf(), "*".to_string()),
+    }
+}
+
+// Return true if the name matches the pattern, * matches any text and ? any character
+fn matches(pattern: &str, name: &str) -> bool {
+    let (pattern, name): (Vec<char>, Vec<char>) =
+        (pattern.chars().collect(), name.chars().collect());
+    // matched[j] is true if the pattern read so far matches the first j characters of the name
+    let mut matched = vec![false; name.len() + 1];
+    matched[0] = true;
+    for p in pattern {
+        let previous = matched.clone();
+        matched[0] = p == '*' && previous[0];
+        for j in 1..=name.len() {
+            matched[j] = match p {
+                '*' => previous[j] || matched[j - 1],
+                '?' => previous[j - 1],
+                c => previous[j - 1] && name[j - 1] == c,
+            };
+        }
+    }
+    matched[name.len()]
+}
+
+// Return the files of the directory that match the pattern, sorted by name
+// The manifest is not an input
+fn list_inputs(
+    dir: &Path,
+    pattern: &str,
+    manifest: Option<&Path>,
+) -> Result<Vec<PathBuf>, InputError> {
+    let entries = std::fs::read_dir(dir).map_err(|e| {
+        InputError::FileError(format!(
+            "error reading directory {}, context {e}",
+            dir.display()
+        ))
+    })?;
+    let mut inputs: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .filter(|path| manifest.is_none_or(|manifest| !same_file(path, manifest)))
+        .filter(|path| {
+            path.file_name()
+                .is_some_and(|name| matches(pattern, &name.to_string_lossy()))
+        })
+        .collect();
+    inputs.sort();
+    Ok(inputs)
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+// Parse the manifest, each line is the name of a maze and its starting points
+// Eg: maze.txt: 0,0 2,4
+// Empty lines and lines starting with # are ignored
+fn parse_manifest(contents: &str) -> Result<HashMap<String, Vec<Point>>, InputError> {
+    let mut manifest = HashMap::new();
+    for line in contents.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((name, points)) = line.split_once(':') else {
+            return Err(InputError::InvalidInput(format!(
+                "invalid manifest line {line}, it should be <maze>: x,y [x,y ...]"
+            )));
+        };
+        manifest.insert(name.trim().to_string(), parse_points(points)?);
+    }
+    Ok(manifest)
+}
+
+// Play the mazes with jobs threads, each one takes the next maze not played yet
+// Return the result of each maze, in the same order as the inputs
+fn play_all(
+    inputs: &[PathBuf],
+    manifest: &HashMap<String, Vec<Point>>,
+    output_dir: &str,
+    strict: bool,
+    jobs: usize,
+) -> Vec<Status> {
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(HashMap::new());
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.min(inputs.len()) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                let Some(input) = inputs.get(index) else {
+                    break;
+                };
+                let status = play_one(input, manifest, output_dir, strict);
+                if let Ok(mut results) = results.lock() {
+                    results.insert(index, status);
+                }
+            });
+        }
+    });
+    let mut results = results.into_inner().unwrap_or_default();
+    (0..inputs.len())
+        .map(|index| {
+            results
+                .remove(&index)
+                .unwrap_or_else(|| Status::FileError("the maze was not played".to_string()))
+        })
+        .collect()
+}
+
+// Play one maze and write its output in the output directory with the same name
+// Like the assignment, errors of the maze are written to the output
+fn play_one(
+    input: &Path,
+    manifest: &HashMap<String, Vec<Point>>,
+    output_dir: &str,
+    strict: bool,
+) -> Status {
+    let name = input
+        .file_name()
+        .map_or(String::new(), |name| name.to_string_lossy().to_string());
+    let output_path = format!("{output_dir}{name}");
+    let contents = match io::read_input(&input.to_string_lossy()) {
+        Ok(contents) => contents,
+        Err(error) => return Status::FileError(error.to_string()),
+    };
+    let (header_points, maze) = match split_header(&contents) {
+        Ok(header) => header,
+        // A bad header is an error of the maze, like an invalid square
+        Err(error) => {
+            let error = error.to_string();
+            return write_output(&output_path, &error, Status::ParseError(error.clone()));
+        }
+    };
+    let start_points = match manifest.get(&name) {
+        Some(points) => points.clone(),
+        None => header_points,
+    };
+    if start_points.is_empty() {
+        return Status::NoStartPoint;
+    }
+
+    let (output, status) = match Bomberman::parse_all(maze, strict) {
+        Err(errors) => {
+            let first = errors.first().map(|e| e.to_string()).unwrap_or_default();
+            (first.clone(), Status::ParseError(first))
+        }
+        Ok(mut game) => match game.play_many(&start_points) {
+            Ok(board) => (board, Status::Ok),
+            Err(errors) => {
+                let lines: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+                (lines.join("\n"), Status::MissingStartBomb(lines.join(", ")))
+            }
+        },
+    };
+    write_output(&output_path, &output, status)
+}
+
+// Write the output of a maze and return its status, or a file error if it can not be written
+fn write_output(output_path: &str, output: &str, status: Status) -> Status {
+    match io::write_file(output_path, output.as_bytes()) {
+        Ok(()) => status,
+        Err(error) => Status::FileError(error.to_string()),
+    }
+}
+
+fn print_summary(inputs: &[PathBuf], results: &[Status]) {
+    let width = inputs
+        .iter()
+        .map(|input| input.to_string_lossy().len())
+        .max()
+        .unwrap_or(0)
+        .max("maze".len());
+    println!("{:<width$} {:<18} detail", "maze", "status");
+    for (input, status) in inputs.iter().zip(results) {
+        let row = format!(
+            "{:<width$} {:<18} {}",
+            input.to_string_lossy(),
+            status.name(),
+            status.detail()
+        );
+        println!("{}", row.trim_end());
+    }
+    let count = |name: &str| results.iter().filter(|s| s.name() == name).count();
+    println!(
+        "\n{} mazes: {} ok, {} parse errors, {} missing start bombs, {} without start point, {} file errors",
+        results.len(),
+        count("ok"),
+        count("parse error"),
+        count("missing start bomb"),
+        count("no start point"),
+        count("file error")
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cli::USAGE_ERROR;
+
+    #[test]
+    fn test_matches_glob() {
+        assert!(matches("*.txt", "maze.txt"));
+        assert!(matches("maze?.txt", "maze1.txt"));
+        assert!(matches("*", ""));
+        assert!(!matches("*.txt", "maze.json"));
+        assert!(!matches("maze?.txt", "maze.txt"));
+    }
+
+    #[test]
+    fn test_split_glob() {
+        assert_eq!(
+            split_glob("mazes/*.txt"),
+            (PathBuf::from("mazes"), "*.txt".to_string())
+        );
+        assert_eq!(
+            split_glob("*.txt"),
+            (PathBuf::from("."), "*.txt".to_string())
+        );
+        assert_eq!(
+            split_glob("mazes"),
+            (PathBuf::from("mazes"), "*".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest = parse_manifest("# suite\nmaze.txt: 0,0 2,4\n\nother.txt: 1,1\n").unwrap();
+        assert_eq!(
+            manifest.get("maze.txt"),
+            Some(&vec![Point::new(0, 0), Point::new(2, 4)])
+        );
+        assert_eq!(manifest.get("other.txt"), Some(&vec![Point::new(1, 1)]));
+        assert!(parse_manifest("maze.txt 0,0").is_err());
+    }
+
+    // Return an empty directory for the test, with an out directory inside
+    // The name has the process id so test runs at the same time do not share it
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("out")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_play_all_reports_each_maze() {
+        let dir = test_dir("bomberman_batch_test");
+        let mazes = [
+            ("a.txt", "# start: 0,0\nB1 F1\n"),
+            ("b.txt", "# start: 1,0\nB1 F1\n"),
+            ("c.txt", "# start: 0,0\nB1 Q\n"),
+            ("d.txt", "B1 F1\n"),
+        ];
+        let inputs: Vec<PathBuf> = mazes
+            .iter()
+            .map(|(name, maze)| {
+                std::fs::write(dir.join(name), maze).unwrap();
+                dir.join(name)
+            })
+            .collect();
+        let output_dir = format!("{}/out/", dir.display());
+
+        let results = play_all(&inputs, &HashMap::new(), &output_dir, false, 3);
+        assert_eq!(results[0], Status::Ok);
+        assert!(matches!(results[1], Status::MissingStartBomb(_)));
+        assert!(matches!(results[2], Status::ParseError(_)));
+        assert_eq!(results[3], Status::NoStartPoint);
+        assert_eq!(
+            std::fs::read_to_string(dir.join("out/a.txt")).unwrap(),
+            "_ _\n"
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_output_dir_same_as_input_is_usage_error() {
+        let dir = test_dir("bomberman_batch_output_test");
+        let same = format!("{}/out/../", dir.display());
+        let error = check_output_dir(&dir, &same);
+        assert_eq!(error.err().map(|e| e.exit_code()), Some(USAGE_ERROR));
+        assert!(check_output_dir(&dir, &format!("{}/out/", dir.display())).is_ok());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_play_one_bad_header_is_parse_error() {
+        let dir = test_dir("bomberman_batch_header_test");
+        let input = dir.join("maze.txt");
+        std::fs::write(&input, "# start: 0;0\nB1 F1\n").unwrap();
+        let output_dir = format!("{}/out/", dir.display());
+
+        let status = play_one(&input, &HashMap::new(), &output_dir, false);
+        assert!(matches!(status, Status::ParseError(_)));
+        assert_eq!(
+            std::fs::read_to_string(dir.join("out/maze.txt")).unwrap(),
+            status.detail()
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
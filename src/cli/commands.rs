@@ -0,0 +1,448 @@
+use crate::cli::flags::{
+    Flags, Format, AFTER, BEFORE, BOMBS, CLASSIC, CLEARABLE, DENY_WARNINGS, ENEMIES, EVENTS, GIF,
+    HEIGHT, INPUT, INPUT_FORMAT, MAX_HEALTH, MAX_RANGE, OUTPUT, OUTPUT_FORMAT, REDIRECTIONS, ROCKS,
+    SEED, SIDE_BY_SIDE, START, STRICT, SVG, WALLS, WIDTH,
+};
+use crate::cli::io::{self, Output};
+use crate::cli::{
+    CliError, BATCH_COMMAND, DIFF_COMMAND, GENERATE_COMMAND, PLAY_COMMAND, RENDER_COMMAND,
+    SOLVE_COMMAND, VALIDATE_COMMAND, VIEW_COMMAND,
+};
+use ej_individual::animation::{self, AnimationError};
+use ej_individual::board_diff;
+use ej_individual::bomberman::Bomberman;
+use ej_individual::generator::{self, GeneratorOptions};
+use ej_individual::input_errors::InputError;
+use ej_individual::lint;
+use ej_individual::solver;
+use ej_individual::svg;
+
+const MAZE_FORMATS: [Format; 2] = [Format::Text, Format::Json];
+const RENDER_FORMATS: [Format; 4] = [Format::Svg, Format::Gif, Format::Json, Format::Text];
+
+const PLAY_HELP: &str = "Usage: ej_individual play --input <file|-> --start x,y [flags]
+
+Play the chain reaction of the maze and write the board after it.
+
+Flags:
+  --input <file|->           maze to play, - reads stdin
+  --start x,y                starting bomb, repeat it to start several bombs in the same turn
+  --output <file|dir/|->     where to write the board, stdout by default. In a directory
+                             the board has the name of the maze
+  --input-format <text|json> format of the maze, text by default
+  --output-format <text|json> format of the board, text by default
+  --strict                   only accept square mazes like the assignment format
+  --events                   write the event log next to the output as <maze>.events.txt
+  --svg                      write the board as an SVG image next to the output
+  --gif                      write the chain reaction as an animated GIF next to the output
+";
+
+const VALIDATE_HELP: &str = "Usage: ej_individual validate --input <file|-> [flags]
+
+Check the maze and print every problem found, with the line it is in.
+If it has none, print the warnings of things that are legal but suspicious:
+bombs no other bomb reaches, enemies no bomb reaches, redirections that send
+the explosion into a wall or out of the maze, and redirection loops.
+
+Flags:
+  --input <file|->           maze to check, - reads stdin
+  --input-format <text|json> format of the maze, text by default
+  --strict                   only accept square mazes like the assignment format
+  --deny-warnings            fail if the maze has warnings, for CI
+";
+
+const SOLVE_HELP: &str = "Usage: ej_individual solve --input <file|-> [flags]
+
+Play the maze from every bomb and print them ranked by kills, destroyed bombs and damage.
+
+Flags:
+  --input <file|->           maze to solve, - reads stdin
+  --input-format <text|json> format of the maze, text by default
+";
+
+const RENDER_HELP: &str = "Usage: ej_individual render --input <file|-> [flags]
+
+Draw the maze. With starting bombs the maze is drawn after the chain reaction,
+with the squares hit by it highlighted.
+
+Flags:
+  --input <file|->           maze to draw, - reads stdin
+  --output <file|dir/|->     where to write the image, stdout by default
+  --input-format <text|json> format of the maze, text by default
+  --output-format <svg|gif|json|text> format of the image, svg by default.
+                             gif animates the chain reaction and needs --start
+  --start x,y                starting bomb, repeat it to start several bombs
+";
+
+pub const VIEW_HELP: &str = "Usage: ej_individual view --input <file> [flags]
+
+Show the maze in the terminal, choose the starting bomb with the arrows and Enter,
+and play the chain reaction turn by turn. q quits.
+
+Flags:
+  --input <file>             maze to show
+  --delay <milliseconds>     time between turns, without it every turn waits for a key
+";
+
+const BATCH_HELP: &str = "Usage: ej_individual batch --input <dir|glob> --output <dir> [flags]
+
+Play every maze of the directory, or the ones matching the glob, in parallel and
+print a summary with the result of each one. Each board is written to the output
+directory with the name of its maze, or its error like the assignment.
+
+The starting bombs of a maze are read from the manifest, with lines like
+maze.txt: 0,0 2,4
+or from a first line in the maze like
+# start: 0,0 2,4
+The manifest wins if a maze has both.
+
+Flags:
+  --input <dir|glob>         mazes to play, eg: mazes/ or 'mazes/*.txt'
+  --output <dir>             directory where the boards are written
+  --manifest <file>          starting bombs of each maze, <dir>/manifest.txt by default
+  --jobs <n>                 number of threads, one per core by default
+  --strict                   only accept square mazes like the assignment format
+";
+
+const DIFF_HELP: &str = "Usage: ej_individual diff --before <file> --after <file> [flags]
+       ej_individual diff --input <file|-> --start x,y [flags]
+
+List each square that changed between two boards, eg: (4, 2): F2 -> _ (killed).
+With --input the maze is compared with the board after playing it.
+
+Flags:
+  --before <file>            board before
+  --after <file>             board after
+  --input <file|->           maze to play and compare, - reads stdin
+  --start x,y                starting bomb, repeat it to start several bombs
+  --input-format <text|json> format of the maze, text by default
+  --side-by-side             also show both boards with the changed squares coloured
+";
+
+const GENERATE_HELP: &str = "Usage: ej_individual generate [flags]
+
+Create a random maze. The same seed and flags always create the same maze.
+
+Flags:
+  --width <n>                number of columns, 7 by default
+  --height <n>               number of rows, 7 by default
+  --walls <0-1>              chance of a square to be a wall, 0.05 by default
+  --rocks <0-1>              chance of a square to be a rock, 0.1 by default
+  --bombs <0-1>              chance of a square to be a bomb, 0.15 by default
+  --enemies <0-1>            chance of a square to be an enemy, 0.1 by default
+  --redirections <0-1>       chance of a square to be a redirection, 0.05 by default
+  --max-range <n>            maximum range of the bombs, 4 by default
+  --max-health <1-3>         maximum health of the enemies, 3 by default
+  --seed <n>                 seed of the maze, random by default and printed to stderr
+  --classic                  put a wall in every square with odd x and y, like the assignment
+  --clearable                only create a maze where some starting bomb kills every enemy,
+                             the starting bomb is printed to stderr
+  --output <file|->          where to write the maze, stdout by default
+  --output-format <text|json> format of the maze, text by default
+";
+
+// Return the help of the command, None if the command does not exist
+pub fn help(command: &str) -> Option<&'static str> {
+    match command {
+        PLAY_COMMAND => Some(PLAY_HELP),
+        VALIDATE_COMMAND => Some(VALIDATE_HELP),
+        SOLVE_COMMAND => Some(SOLVE_HELP),
+        RENDER_COMMAND => Some(RENDER_HELP),
+        VIEW_COMMAND => Some(VIEW_HELP),
+        BATCH_COMMAND => Some(BATCH_HELP),
+        DIFF_COMMAND => Some(DIFF_HELP),
+        GENERATE_COMMAND => Some(GENERATE_HELP),
+        _ => None,
+    }
+}
+
+// Read and parse the maze of the --input flag
+// Return the game and the contents of the maze, or every error found in it
+pub fn load_game(flags: &Flags) -> Result<(Bomberman, String), CliError> {
+    let contents = io::read_input(flags.required(INPUT)?)?;
+    let game = match flags.format(INPUT_FORMAT, &MAZE_FORMATS)? {
+        Format::Json => Bomberman::from_json(&contents).map_err(|error| vec![error]),
+        _ => Bomberman::parse_all(&contents, flags.has(STRICT)),
+    };
+    match game {
+        Ok(game) => Ok((game, contents)),
+        Err(errors) => Err(CliError::Maze {
+            errors,
+            source: contents,
+        }),
+    }
+}
+
+// Play the chain reaction of the maze and write the board after it
+// Like the assignment, when the output is a file the errors are also written to it
+pub fn play(args: &[String]) -> Result<(), CliError> {
+    let flags = Flags::parse(
+        args,
+        &[INPUT, OUTPUT, START, INPUT_FORMAT, OUTPUT_FORMAT],
+        &[STRICT, EVENTS, SVG, GIF],
+    )?;
+    let input = flags.required(INPUT)?;
+    let start_points = flags.start_points()?;
+    if start_points.is_empty() {
+        return Err(InputError::InvalidInput(format!("missing required flag {START}")).into());
+    }
+    let output_format = flags.format(OUTPUT_FORMAT, &MAZE_FORMATS)?;
+    let output = Output::new(flags.value(OUTPUT), input, output_format)?;
+    let events_path = flags
+        .has(EVENTS)
+        .then(|| output.sidecar("events.txt", EVENTS))
+        .transpose()?;
+    let svg_path = flags
+        .has(SVG)
+        .then(|| output.sidecar("svg", SVG))
+        .transpose()?;
+    let gif_path = flags
+        .has(GIF)
+        .then(|| output.sidecar("gif", GIF))
+        .transpose()?;
+
+    let (mut game, contents) = match load_game(&flags) {
+        Ok(loaded) => loaded,
+        Err(error) => {
+            if let (Output::File(_), CliError::Maze { errors, .. }) = (&output, &error) {
+                if let Some(first) = errors.first() {
+                    output.write(first.to_string().as_bytes())?;
+                }
+            }
+            return Err(error);
+        }
+    };
+
+    if let Some(gif_path) = gif_path {
+        // The animation plays its own copy, errors of the maze are reported by the game below
+        match animation::animate(&game, &start_points) {
+            Ok(gif) => io::write_file(&gif_path, &gif)?,
+            Err(AnimationError::Maze(_)) => (),
+            Err(error) => return Err(InputError::InvalidInput(error.to_string()).into()),
+        }
+    }
+
+    match game.play_many_with_events(&start_points) {
+        Ok((board, events)) => {
+            if let Some(events_path) = events_path {
+                let log: Vec<String> = events.iter().map(|event| event.to_string()).collect();
+                io::write_file(&events_path, log.join("\n").as_bytes())?;
+            }
+            if let Some(svg_path) = svg_path {
+                let svg = game.to_svg(&svg::blast_squares(&events));
+                io::write_file(&svg_path, svg.as_bytes())?;
+            }
+            let board = match output_format {
+                Format::Json => game.to_json(),
+                _ => board,
+            };
+            output.write(board.as_bytes())?;
+            Ok(())
+        }
+        Err(errors) => {
+            if let Output::File(_) = output {
+                let lines: Vec<String> = errors.iter().map(|error| error.to_string()).collect();
+                output.write(lines.join("\n").as_bytes())?;
+            }
+            Err(CliError::Maze {
+                errors,
+                source: contents,
+            })
+        }
+    }
+}
+
+// Check the maze and print every problem found, or its warnings if it has no problems
+pub fn validate(args: &[String]) -> Result<(), CliError> {
+    let flags = Flags::parse(args, &[INPUT, INPUT_FORMAT], &[STRICT, DENY_WARNINGS])?;
+    let (game, _) = load_game(&flags)?;
+    let input = flags.required(INPUT)?;
+    let warnings = lint::lint(&game);
+    for warning in &warnings {
+        println!("warning: {warning}");
+    }
+    match warnings.len() {
+        0 => println!("{input}: ok"),
+        count => println!("{input}: ok with {count} warnings"),
+    }
+    if flags.has(DENY_WARNINGS) && !warnings.is_empty() {
+        return Err(CliError::Warnings {
+            count: warnings.len(),
+        });
+    }
+    Ok(())
+}
+
+// Print the starting bombs of the maze ranked by kills, destroyed bombs and damage
+pub fn solve(args: &[String]) -> Result<(), CliError> {
+    let flags = Flags::parse(args, &[INPUT, INPUT_FORMAT], &[])?;
+    let (game, _) = load_game(&flags)?;
+
+    println!(
+        "{:>4} {:>10} {:>6} {:>7} {:>6} {:>10}",
+        "rank", "start", "kills", "damage", "bombs", "remaining"
+    );
+    for (rank, solution) in solver::solve(&game).iter().enumerate() {
+        println!(
+            "{:>4} {:>10} {:>6} {:>7} {:>6} {:>10}",
+            rank + 1,
+            solution.start_bomb.to_string(),
+            solution.kills,
+            solution.damage,
+            solution.destroyed_bombs,
+            solution.remaining_enemies
+        );
+    }
+    Ok(())
+}
+
+// Draw the maze, after the chain reaction if starting bombs are given
+pub fn render(args: &[String]) -> Result<(), CliError> {
+    let flags = Flags::parse(
+        args,
+        &[INPUT, OUTPUT, START, INPUT_FORMAT, OUTPUT_FORMAT],
+        &[],
+    )?;
+    let format = match flags.value(OUTPUT_FORMAT) {
+        Some(format) => Format::new(format, &RENDER_FORMATS)?,
+        None => Format::Svg,
+    };
+    let output = Output::new(flags.value(OUTPUT), flags.required(INPUT)?, format)?;
+    let start_points = flags.start_points()?;
+    let (mut game, contents) = load_game(&flags)?;
+    let maze_error = |errors| CliError::Maze {
+        errors,
+        source: contents.clone(),
+    };
+
+    if format == Format::Gif {
+        if start_points.is_empty() {
+            return Err(InputError::InvalidInput(format!("gif needs at least one {START}")).into());
+        }
+        let gif = animation::animate(&game, &start_points).map_err(|error| match error {
+            AnimationError::Maze(errors) => maze_error(errors),
+            error => InputError::InvalidInput(error.to_string()).into(),
+        })?;
+        return Ok(output.write(&gif)?);
+    }
+
+    let mut blast = Vec::new();
+    if !start_points.is_empty() {
+        let (_, events) = game
+            .play_many_with_events(&start_points)
+            .map_err(maze_error)?;
+        blast = svg::blast_squares(&events);
+    }
+    let image = match format {
+        Format::Json => game.to_json(),
+        Format::Text => game.to_string(),
+        _ => game.to_svg(&blast),
+    };
+    Ok(output.write(image.as_bytes())?)
+}
+
+// Print the squares that changed between two boards, or between a maze and its board after
+// playing it
+pub fn diff(args: &[String]) -> Result<(), CliError> {
+    let flags = Flags::parse(
+        args,
+        &[BEFORE, AFTER, INPUT, START, INPUT_FORMAT],
+        &[SIDE_BY_SIDE],
+    )?;
+    let (before, after) = match (flags.value(BEFORE), flags.value(AFTER)) {
+        (Some(before), Some(after)) => (io::read_input(before)?, io::read_input(after)?),
+        (None, None) => {
+            let start_points = flags.start_points()?;
+            if start_points.is_empty() {
+                return Err(
+                    InputError::InvalidInput(format!("missing required flag {START}")).into(),
+                );
+            }
+            let (mut game, contents) = load_game(&flags)?;
+            let before = game.to_string();
+            let after = game
+                .play_many(&start_points)
+                .map_err(|errors| CliError::Maze {
+                    errors,
+                    source: contents,
+                })?;
+            (before, after)
+        }
+        _ => {
+            return Err(InputError::InvalidInput(format!(
+                "{BEFORE} and {AFTER} must be given together"
+            ))
+            .into())
+        }
+    };
+
+    let changes = board_diff::diff(&before, &after).map_err(InputError::InvalidInput)?;
+    if flags.has(SIDE_BY_SIDE) {
+        let view = board_diff::side_by_side(&before, &after).map_err(InputError::InvalidInput)?;
+        println!("{view}");
+    }
+    for change in &changes {
+        println!("{change}");
+    }
+    println!("{} squares changed", changes.len());
+    Ok(())
+}
+
+// Create a random maze and write it
+pub fn generate(args: &[String]) -> Result<(), CliError> {
+    let flags = Flags::parse(
+        args,
+        &[
+            WIDTH,
+            HEIGHT,
+            WALLS,
+            ROCKS,
+            BOMBS,
+            ENEMIES,
+            REDIRECTIONS,
+            MAX_RANGE,
+            MAX_HEALTH,
+            SEED,
+            OUTPUT,
+            OUTPUT_FORMAT,
+        ],
+        &[CLASSIC, CLEARABLE],
+    )?;
+    let defaults = GeneratorOptions::default();
+    let seed = match flags.value(SEED) {
+        Some(_) => flags.parse_or(SEED, defaults.seed)?,
+        None => {
+            let seed = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(defaults.seed, |time| time.as_nanos() as u64);
+            eprintln!("seed {seed}");
+            seed
+        }
+    };
+    let options = GeneratorOptions {
+        width: flags.parse_or(WIDTH, defaults.width)?,
+        height: flags.parse_or(HEIGHT, defaults.height)?,
+        walls: flags.parse_or(WALLS, defaults.walls)?,
+        rocks: flags.parse_or(ROCKS, defaults.rocks)?,
+        bombs: flags.parse_or(BOMBS, defaults.bombs)?,
+        enemies: flags.parse_or(ENEMIES, defaults.enemies)?,
+        redirections: flags.parse_or(REDIRECTIONS, defaults.redirections)?,
+        max_range: flags.parse_or(MAX_RANGE, defaults.max_range)?,
+        max_health: flags.parse_or(MAX_HEALTH, defaults.max_health)?,
+        seed,
+        classic_walls: flags.has(CLASSIC),
+        clearable: flags.has(CLEARABLE),
+    };
+    let format = flags.format(OUTPUT_FORMAT, &MAZE_FORMATS)?;
+    let output = Output::new(flags.value(OUTPUT), "generated.txt", format)?;
+
+    let (game, start_bomb) = generator::generate(&options).map_err(InputError::InvalidInput)?;
+    if let Some(start_bomb) = start_bomb {
+        eprintln!("clearable from {},{}", start_bomb.x, start_bomb.y);
+    }
+    let maze = match format {
+        Format::Json => game.to_json(),
+        _ => game.to_string(),
+    };
+    Ok(output.write(maze.as_bytes())?)
+}
@@ -0,0 +1,255 @@
+use ej_individual::input_errors::InputError;
+use ej_individual::maze_header;
+use ej_individual::utils::point::Point;
+
+pub const INPUT: &str = "--input";
+pub const OUTPUT: &str = "--output";
+pub const START: &str = "--start";
+pub const INPUT_FORMAT: &str = "--input-format";
+pub const OUTPUT_FORMAT: &str = "--output-format";
+pub const DELAY: &str = "--delay";
+pub const STRICT: &str = "--strict";
+pub const EVENTS: &str = "--events";
+pub const SVG: &str = "--svg";
+pub const GIF: &str = "--gif";
+pub const MANIFEST: &str = "--manifest";
+pub const JOBS: &str = "--jobs";
+pub const BEFORE: &str = "--before";
+pub const AFTER: &str = "--after";
+pub const SIDE_BY_SIDE: &str = "--side-by-side";
+pub const DENY_WARNINGS: &str = "--deny-warnings";
+pub const WIDTH: &str = "--width";
+pub const HEIGHT: &str = "--height";
+pub const WALLS: &str = "--walls";
+pub const ROCKS: &str = "--rocks";
+pub const BOMBS: &str = "--bombs";
+pub const ENEMIES: &str = "--enemies";
+pub const REDIRECTIONS: &str = "--redirections";
+pub const MAX_RANGE: &str = "--max-range";
+pub const MAX_HEALTH: &str = "--max-health";
+pub const SEED: &str = "--seed";
+pub const CLASSIC: &str = "--classic";
+pub const CLEARABLE: &str = "--clearable";
+pub const HELP: &str = "--help";
+pub const SHORT_HELP: &str = "-h";
+
+// Format of the files read and written
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Svg,
+    Gif,
+}
+
+impl Format {
+    // Parse the format, only the allowed ones are accepted
+    pub fn new(format: &str, allowed: &[Format]) -> Result<Format, InputError> {
+        let parsed = match format {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            "svg" => Some(Format::Svg),
+            "gif" => Some(Format::Gif),
+            _ => None,
+        };
+        match parsed {
+            Some(format) if allowed.contains(&format) => Ok(format),
+            _ => {
+                let allowed: Vec<&str> = allowed.iter().map(Format::name).collect();
+                Err(InputError::InvalidInput(format!(
+                    "unknown format {format}, valid formats are {}",
+                    allowed.join(", ")
+                )))
+            }
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Format::Text => "text",
+            Format::Json => "json",
+            Format::Svg => "svg",
+            Format::Gif => "gif",
+        }
+    }
+
+    // Return the extension of the files written in this format, text keeps the input name
+    pub fn extension(&self) -> Option<&'static str> {
+        match self {
+            Format::Text => None,
+            Format::Json => Some("json"),
+            Format::Svg => Some("svg"),
+            Format::Gif => Some("gif"),
+        }
+    }
+}
+
+// Named flags given to a command, in the order they were given
+// Flags with a value can be written as --flag value or --flag=value
+#[derive(Debug, Default, PartialEq)]
+pub struct Flags {
+    values: Vec<(String, String)>,
+    switches: Vec<String>,
+}
+
+impl Flags {
+    // Parse the arguments of a command
+    // Return an error for unknown flags, positional arguments and flags missing their value
+    pub fn parse(
+        args: &[String],
+        value_flags: &[&str],
+        switches: &[&str],
+    ) -> Result<Flags, InputError> {
+        let mut flags = Flags::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            if value_flags.contains(&name) {
+                let Some(value) = inline_value.or_else(|| args.next().cloned()) else {
+                    return Err(InputError::InvalidInput(format!(
+                        "missing value after {name}"
+                    )));
+                };
+                flags.values.push((name.to_string(), value));
+            } else if switches.contains(&name) && inline_value.is_none() {
+                flags.switches.push(name.to_string());
+            } else {
+                let mut valid: Vec<&str> = value_flags.to_vec();
+                valid.extend(switches);
+                return Err(InputError::InvalidInput(format!(
+                    "unknown argument {arg}, valid flags are {}",
+                    valid.join(", ")
+                )));
+            }
+        }
+        Ok(flags)
+    }
+
+    // Return the last value given to the flag
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.values
+            .iter()
+            .rev()
+            .find(|(flag, _)| flag == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    // Return the value of a flag that must be given
+    pub fn required(&self, name: &str) -> Result<&str, InputError> {
+        self.value(name)
+            .ok_or_else(|| InputError::InvalidInput(format!("missing required flag {name}")))
+    }
+
+    // Return every value given to the flag, in order
+    pub fn values(&self, name: &str) -> Vec<&str> {
+        self.values
+            .iter()
+            .filter(|(flag, _)| flag == name)
+            .map(|(_, value)| value.as_str())
+            .collect()
+    }
+
+    // Return true if the switch was given
+    pub fn has(&self, name: &str) -> bool {
+        self.switches.iter().any(|switch| switch == name)
+    }
+
+    // Return the format given to the flag, or text if it was not given
+    pub fn format(&self, name: &str, allowed: &[Format]) -> Result<Format, InputError> {
+        match self.value(name) {
+            Some(format) => Format::new(format, allowed),
+            None => Ok(Format::default()),
+        }
+    }
+
+    // Parse the value of the flag, or return the default if it was not given
+    pub fn parse_or<T: std::str::FromStr>(&self, name: &str, default: T) -> Result<T, InputError> {
+        match self.value(name) {
+            Some(value) => value
+                .parse::<T>()
+                .map_err(|_| InputError::InvalidInput(format!("invalid value {value} for {name}"))),
+            None => Ok(default),
+        }
+    }
+
+    // Return the starting points given with --start x,y
+    pub fn start_points(&self) -> Result<Vec<Point>, InputError> {
+        self.values(START)
+            .into_iter()
+            .map(maze_header::parse_point)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_values_and_switches() {
+        let flags = Flags::parse(
+            &args(&[
+                "--input",
+                "maze.txt",
+                "--start=1,2",
+                "--strict",
+                "--start",
+                "3,4",
+            ]),
+            &[INPUT, START],
+            &[STRICT],
+        )
+        .unwrap();
+        assert_eq!(flags.value(INPUT), Some("maze.txt"));
+        assert_eq!(flags.values(START), vec!["1,2", "3,4"]);
+        assert!(flags.has(STRICT));
+        assert!(!flags.has(EVENTS));
+        assert_eq!(
+            flags.start_points().ok(),
+            Some(vec![Point::new(1, 2), Point::new(3, 4)])
+        );
+    }
+
+    #[test]
+    fn test_parse_unknown_flag_is_error() {
+        let error = Flags::parse(&args(&["--nope"]), &[INPUT], &[STRICT]);
+        assert_eq!(
+            error.err().map(|e| e.to_string()),
+            Some(
+                "InvalidInput: unknown argument --nope, valid flags are --input, --strict"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_missing_value_is_error() {
+        assert!(Flags::parse(&args(&["--input"]), &[INPUT], &[]).is_err());
+    }
+
+    #[test]
+    fn test_parse_or_default() {
+        let flags = Flags::parse(&args(&["--delay", "20"]), &[DELAY, SEED], &[]).unwrap();
+        assert_eq!(flags.parse_or(DELAY, 0u64).ok(), Some(20));
+        assert_eq!(flags.parse_or(SEED, 7u64).ok(), Some(7));
+        let flags = Flags::parse(&args(&["--delay", "x"]), &[DELAY], &[]).unwrap();
+        assert!(flags.parse_or(DELAY, 0u64).is_err());
+    }
+
+    #[test]
+    fn test_format_only_accepts_allowed() {
+        assert_eq!(
+            Format::new("json", &[Format::Text, Format::Json]).ok(),
+            Some(Format::Json)
+        );
+        assert!(Format::new("svg", &[Format::Text, Format::Json]).is_err());
+    }
+}
@@ -0,0 +1,171 @@
+use crate::cli::flags::Format;
+use ej_individual::input_errors::InputError;
+use std::io::{Read, Write};
+use std::path::Path;
+
+// Path that means stdin as input and stdout as output
+pub const STDIO: &str = "-";
+
+// File name used for the outputs derived from stdin
+const STDIN_NAME: &str = "stdin.txt";
+
+// Where the output of a command goes
+#[derive(Debug, PartialEq)]
+pub enum Output {
+    Stdout,
+    File(String),
+}
+
+impl Output {
+    // Resolve the output of the given input
+    //  - Nothing or - is stdout
+    //  - A path ending in / or an existing directory is a directory, it is created if it
+    //    does not exist and the output has the name of the input file. Formats other than
+    //    text change its extension. Eg: maze.txt -> out/maze.json
+    //  - Anything else is the output file
+    pub fn new(output: Option<&str>, input: &str, format: Format) -> Result<Output, InputError> {
+        let output = match output {
+            None | Some(STDIO) => return Ok(Output::Stdout),
+            Some(output) => output,
+        };
+        if !output.ends_with('/') && !Path::new(output).is_dir() {
+            return Ok(Output::File(output.to_string()));
+        }
+        create_dir(output)?;
+        let name = match input {
+            STDIO => STDIN_NAME,
+            input => input.split('/').next_back().unwrap_or(input),
+        };
+        let path = Path::new(output).join(name);
+        let path = match format.extension() {
+            Some(extension) => path.with_extension(extension),
+            None => path,
+        };
+        Ok(Output::File(path.to_string_lossy().to_string()))
+    }
+
+    // Return the path of a file written next to the output with another extension
+    // Eg: out/maze.txt -> out/maze.events.txt
+    // Return an error if the output is stdout, naming the flag that needs the file
+    pub fn sidecar(&self, extension: &str, flag: &str) -> Result<String, InputError> {
+        match self {
+            Output::File(path) => Ok(Path::new(path)
+                .with_extension(extension)
+                .to_string_lossy()
+                .to_string()),
+            Output::Stdout => Err(InputError::InvalidInput(format!(
+                "{flag} needs {} to be a file or a directory",
+                crate::cli::flags::OUTPUT
+            ))),
+        }
+    }
+
+    // Write the contents to the output
+    pub fn write(&self, contents: &[u8]) -> Result<(), InputError> {
+        match self {
+            Output::Stdout => std::io::stdout().write_all(contents).map_err(|e| {
+                InputError::FileError(format!("error writing to stdout, context {e}"))
+            }),
+            Output::File(path) => write_file(path, contents),
+        }
+    }
+}
+
+// Format the output path to remove leading and trailing slashes if they exist
+// Return the formatted path like ./{path}/
+pub fn format_out_path(path: &str) -> String {
+    let mut path = path.trim_end_matches('/');
+    path = path.trim_start_matches('/');
+    format!("./{path}/")
+}
+
+// Create a directory if it doesn't exist
+pub fn create_dir(path: &str) -> Result<(), InputError> {
+    if Path::new(path).exists() {
+        return Ok(());
+    }
+
+    match std::fs::create_dir_all(path) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(InputError::FileError(format!(
+            "error creating directory {path}, context {e}"
+        ))),
+    }
+}
+
+// Read the input file, or stdin if the path is -
+pub fn read_input(path: &str) -> Result<String, InputError> {
+    if path == STDIO {
+        let mut contents = String::new();
+        return match std::io::stdin().read_to_string(&mut contents) {
+            Ok(_) => Ok(contents),
+            Err(e) => Err(InputError::FileError(format!(
+                "error reading stdin, context {e}"
+            ))),
+        };
+    }
+    match std::fs::read_to_string(path) {
+        Ok(contents) => Ok(contents),
+        Err(e) => Err(InputError::FileError(format!(
+            "error reading file {path}, context {e}"
+        ))),
+    }
+}
+
+// Write contents to file
+pub fn write_file(path: &str, contents: &[u8]) -> Result<(), InputError> {
+    match std::fs::write(path, contents) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(InputError::FileError(format!(
+            "error writing file {path}, context {e}"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_output_defaults_to_stdout() {
+        assert_eq!(
+            Output::new(None, "maze.txt", Format::Text).ok(),
+            Some(Output::Stdout)
+        );
+        assert_eq!(
+            Output::new(Some("-"), "maze.txt", Format::Text).ok(),
+            Some(Output::Stdout)
+        );
+    }
+
+    #[test]
+    fn test_output_file_is_kept() {
+        assert_eq!(
+            Output::new(Some("board.txt"), "maze.txt", Format::Json).ok(),
+            Some(Output::File("board.txt".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_output_directory_uses_input_name() {
+        // The name has the process id so test runs at the same time do not share it
+        let path =
+            std::env::temp_dir().join(format!("bomberman_cli_output_test_{}/", std::process::id()));
+        let dir = path.to_string_lossy().to_string();
+        let output = Output::new(Some(&dir), "mazes/maze.txt", Format::Json).ok();
+        assert_eq!(output, Some(Output::File(format!("{dir}maze.json"))));
+        let sidecar = output.and_then(|output| output.sidecar("events.txt", "--events").ok());
+        assert_eq!(sidecar, Some(format!("{dir}maze.events.txt")));
+        let _ = std::fs::remove_dir_all(&path);
+    }
+
+    #[test]
+    fn test_sidecar_of_stdout_is_error() {
+        assert!(Output::Stdout.sidecar("svg", "--svg").is_err());
+    }
+
+    #[test]
+    fn test_format_out_path() {
+        assert_eq!(format_out_path("/out/dir/"), "./out/dir/");
+    }
+}
@@ -0,0 +1,295 @@
+mod batch;
+mod commands;
+mod flags;
+mod io;
+mod view;
+
+use ej_individual::bomberman_errors::BombermanError;
+use ej_individual::input_errors::InputError;
+use std::process::ExitCode;
+
+pub const PLAY_COMMAND: &str = "play";
+pub const VALIDATE_COMMAND: &str = "validate";
+pub const SOLVE_COMMAND: &str = "solve";
+pub const RENDER_COMMAND: &str = "render";
+pub const VIEW_COMMAND: &str = "view";
+pub const BATCH_COMMAND: &str = "batch";
+pub const DIFF_COMMAND: &str = "diff";
+pub const GENERATE_COMMAND: &str = "generate";
+
+// Exit codes, 0 is success
+const BATCH_FAILED: u8 = 1;
+const USAGE_ERROR: u8 = 2;
+const FILE_ERROR: u8 = 3;
+const MAZE_NOT_SQUARE: u8 = 10;
+const MAZE_NOT_RECTANGULAR: u8 = 11;
+const INVALID_SQUARE: u8 = 12;
+const NO_BOMB_IN_STARTING_POSITION: u8 = 13;
+const INVALID_JSON: u8 = 14;
+const DENIED_WARNINGS: u8 = 15;
+const UNPAIRED_TELEPORTER: u8 = 16;
+
+const USAGE: &str = "Usage: ej_individual <command> [flags]
+
+Commands:
+  play      Play the chain reaction of a maze and write the board after it
+  validate  Check a maze and print every problem and warning found
+  solve     Rank every starting bomb of a maze by the damage it does
+  render    Draw a maze as SVG, GIF, JSON or text
+  view      Show a maze in the terminal and play it turn by turn
+  batch     Play every maze of a directory and print a summary
+  diff      List the squares that changed between two boards
+  generate  Create a random maze from a seed
+
+Run ej_individual <command> --help for the flags of each command.
+The assignment form ej_individual maze.txt out/ x y [flags] is the same as
+ej_individual play --input ./maze.txt --output ./out/ --start x,y [flags]
+
+Exit codes:
+  0   success
+  1   some mazes of the batch failed
+  2   invalid arguments
+  3   file could not be read or written
+  10  maze is not square (--strict)
+  11  maze rows have different lengths
+  12  invalid square
+  13  no bomb in a starting position
+  14  invalid JSON maze
+  15  warnings in the maze with --deny-warnings
+  16  teleporter without exactly one partner
+";
+
+// Error that stops a command
+pub enum CliError {
+    Input(InputError),
+    // Errors of the maze, with the maze they refer to so they can be rendered
+    Maze {
+        errors: Vec<BombermanError>,
+        source: String,
+    },
+    // Mazes of a batch that could not be played, already reported in its summary
+    Batch {
+        failed: usize,
+    },
+    // Warnings of a maze validated with --deny-warnings, already printed
+    Warnings {
+        count: usize,
+    },
+}
+
+impl From<InputError> for CliError {
+    fn from(error: InputError) -> CliError {
+        CliError::Input(error)
+    }
+}
+
+impl CliError {
+    // Return the exit code of the error, the one of the first error of the maze
+    fn exit_code(&self) -> u8 {
+        match self {
+            CliError::Input(InputError::InvalidInput(_)) => USAGE_ERROR,
+            CliError::Input(InputError::FileError(_)) => FILE_ERROR,
+            CliError::Maze { errors, .. } => match errors.first() {
+                Some(BombermanError::MazeNotSquare { .. }) => MAZE_NOT_SQUARE,
+                Some(BombermanError::MazeNotRectangular { .. }) => MAZE_NOT_RECTANGULAR,
+                Some(BombermanError::InvalidSquare { .. }) => INVALID_SQUARE,
+                Some(BombermanError::NoBombInStartingPosition(_)) => NO_BOMB_IN_STARTING_POSITION,
+                Some(BombermanError::InvalidJson(_)) => INVALID_JSON,
+                Some(BombermanError::UnpairedTeleporter { .. }) => UNPAIRED_TELEPORTER,
+                None => USAGE_ERROR,
+            },
+            CliError::Batch { .. } => BATCH_FAILED,
+            CliError::Warnings { .. } => DENIED_WARNINGS,
+        }
+    }
+
+    // Print the error to stderr, errors of the maze show the line they happened in
+    fn print(&self) {
+        match self {
+            CliError::Input(error) => eprintln!("{error}"),
+            CliError::Maze { errors, source } => {
+                for error in errors {
+                    eprintln!("{}", error.render(source));
+                }
+            }
+            CliError::Batch { failed } => eprintln!("{failed} mazes could not be played"),
+            CliError::Warnings { count } => {
+                eprintln!(
+                    "{count} warnings found and {} was given",
+                    flags::DENY_WARNINGS
+                )
+            }
+        }
+    }
+}
+
+// Run the command line and return the exit code
+pub fn run(args: &[String]) -> ExitCode {
+    let result = match args.first().map(String::as_str) {
+        None => {
+            eprint!("{USAGE}");
+            return ExitCode::from(USAGE_ERROR);
+        }
+        Some(flags::HELP) | Some(flags::SHORT_HELP) => {
+            print!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Some(command) if args[1..].iter().any(|arg| is_help(arg)) => {
+            match commands::help(command) {
+                Some(help) => {
+                    print!("{help}");
+                    return ExitCode::SUCCESS;
+                }
+                None => Err(unknown_command(command)),
+            }
+        }
+        Some(PLAY_COMMAND) => commands::play(&args[1..]),
+        Some(VALIDATE_COMMAND) => commands::validate(&args[1..]),
+        Some(SOLVE_COMMAND) => commands::solve(&args[1..]),
+        Some(RENDER_COMMAND) => commands::render(&args[1..]),
+        Some(VIEW_COMMAND) => view::run(&args[1..]),
+        Some(BATCH_COMMAND) => batch::run(&args[1..]),
+        Some(DIFF_COMMAND) => commands::diff(&args[1..]),
+        Some(GENERATE_COMMAND) => commands::generate(&args[1..]),
+        Some(command) if command.starts_with('-') => Err(unknown_command(command)),
+        Some(_) => assignment_args(args).and_then(|args| commands::play(&args)),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            error.print();
+            ExitCode::from(error.exit_code())
+        }
+    }
+}
+
+fn is_help(arg: &str) -> bool {
+    arg == flags::HELP || arg == flags::SHORT_HELP
+}
+
+fn unknown_command(command: &str) -> CliError {
+    CliError::Input(InputError::InvalidInput(format!(
+        "unknown command {command}, run with {} to see the commands",
+        flags::HELP
+    )))
+}
+
+// Convert the assignment form maze.txt out/ x y [x y ...] [flags] to the flags of play
+// Paths are made relative to the current directory like the assignment expects
+fn assignment_args(args: &[String]) -> Result<Vec<String>, CliError> {
+    if args.len() < 4 {
+        return Err(CliError::Input(InputError::InvalidInput(format!(
+            "incorrect number of arguments provided, need 4 got {}",
+            args.len()
+        ))));
+    }
+    if args[1].starts_with("--") {
+        return Err(CliError::Input(InputError::InvalidInput(format!(
+            "missing output directory before {}",
+            args[1]
+        ))));
+    }
+    // The flags start after the maze and the output
+    let flags_start = args[2..]
+        .iter()
+        .position(|arg| arg.starts_with("--"))
+        .map_or(args.len(), |position| position + 2);
+    let coordinates = &args[2..flags_start];
+    if !coordinates.len().is_multiple_of(2) {
+        return Err(CliError::Input(InputError::InvalidInput(format!(
+            "invalid starting points, need x y pairs got {} coordinates",
+            coordinates.len()
+        ))));
+    }
+
+    let mut play_args = vec![
+        flags::INPUT.to_string(),
+        format!("./{}", args[0].trim_start_matches('/')),
+        flags::OUTPUT.to_string(),
+        io::format_out_path(&args[1]),
+    ];
+    for pair in coordinates.chunks(2) {
+        play_args.push(flags::START.to_string());
+        play_args.push(format!("{},{}", pair[0], pair[1]));
+    }
+    play_args.extend_from_slice(&args[flags_start..]);
+    Ok(play_args)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_assignment_args_are_converted_to_play_flags() {
+        let converted = assignment_args(&args(&[
+            "/maze.txt",
+            "out/",
+            "0",
+            "1",
+            "2",
+            "3",
+            "--strict",
+        ]));
+        assert_eq!(
+            converted.ok(),
+            Some(args(&[
+                "--input",
+                "./maze.txt",
+                "--output",
+                "./out/",
+                "--start",
+                "0,1",
+                "--start",
+                "2,3",
+                "--strict"
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_assignment_args_with_odd_coordinates_is_usage_error() {
+        let error = assignment_args(&args(&["maze.txt", "out/", "0", "1", "2"]));
+        assert_eq!(error.err().map(|e| e.exit_code()), Some(USAGE_ERROR));
+    }
+
+    #[test]
+    fn test_assignment_args_with_flags_before_coordinates_is_usage_error() {
+        let error = assignment_args(&args(&["maze.txt", "--strict", "0", "1"]));
+        assert_eq!(error.err().map(|e| e.exit_code()), Some(USAGE_ERROR));
+    }
+
+    #[test]
+    fn test_each_maze_error_has_its_exit_code() {
+        let maze_error = |error| CliError::Maze {
+            errors: vec![error],
+            source: String::new(),
+        };
+        let point = ej_individual::utils::point::Point::new(0, 0);
+        assert_eq!(
+            maze_error(BombermanError::NoBombInStartingPosition(point)).exit_code(),
+            NO_BOMB_IN_STARTING_POSITION
+        );
+        assert_eq!(
+            maze_error(BombermanError::invalid_square(point, "X", "invalid")).exit_code(),
+            INVALID_SQUARE
+        );
+        assert_eq!(
+            maze_error(BombermanError::UnpairedTeleporter {
+                id: 1,
+                count: 1,
+                location: ej_individual::bomberman_errors::Location::new(point, "T1"),
+            })
+            .exit_code(),
+            UNPAIRED_TELEPORTER
+        );
+        assert_eq!(
+            CliError::Input(InputError::FileError(String::new())).exit_code(),
+            FILE_ERROR
+        );
+    }
+}
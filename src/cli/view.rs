@@ -0,0 +1,233 @@
+use crate::cli::commands::load_game;
+use crate::cli::flags::{Flags, DELAY, INPUT};
+use crate::cli::CliError;
+use ej_individual::ansi::{self, Key};
+use ej_individual::bomberman::Bomberman;
+use ej_individual::input_errors::InputError;
+use ej_individual::simulation::Simulation;
+use ej_individual::svg;
+use ej_individual::utils::point::Point;
+use std::io::Read;
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
+
+// Longest wait for the rest of an escape sequence, the terminal sends the bytes
+// of an arrow together so only a lone Esc waits for it
+const ESCAPE_TIMEOUT: Duration = Duration::from_millis(50);
+
+// Puts the terminal in raw mode with stty and restores it when dropped
+// The standard library can not change the terminal settings, and keys have to be read
+// as they are pressed, without waiting for Enter. stty is part of every Unix system,
+// so it is used instead of a dependency and view fails with a clear error without it
+struct RawMode {
+    saved: String,
+}
+
+impl RawMode {
+    fn enable() -> Result<RawMode, InputError> {
+        let saved = stty(&["-g"])?;
+        stty(&["raw", "-echo"])?;
+        Ok(RawMode {
+            saved: saved.trim().to_string(),
+        })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = stty(&[&self.saved]);
+        print!("{}", ansi::SHOW_CURSOR);
+    }
+}
+
+// Run stty on the terminal of the program and return its output
+// Return an error if stty is not installed or the input is not a terminal
+fn stty(args: &[&str]) -> Result<String, InputError> {
+    let output = std::process::Command::new("stty")
+        .args(args)
+        .stdin(std::process::Stdio::inherit())
+        .output();
+    match output {
+        Ok(output) if output.status.success() => {
+            Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        }
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            Err(InputError::InvalidInput(
+                "could not find the stty program, view needs it to read the keys as they are pressed"
+                    .to_string(),
+            ))
+        }
+        _ => Err(InputError::InvalidInput(
+            "could not set up the terminal, view needs an interactive terminal".to_string(),
+        )),
+    }
+}
+
+// Bytes typed in the terminal, read by a thread so the bytes after an Esc can be
+// waited for with a timeout. A lone Esc then ends the input of read_key instead of
+// blocking until the next key
+struct TerminalInput {
+    bytes: Receiver<u8>,
+    // Bytes of an escape sequence that can still follow
+    sequence: usize,
+}
+
+impl TerminalInput {
+    fn new(bytes: Receiver<u8>) -> TerminalInput {
+        TerminalInput { bytes, sequence: 0 }
+    }
+
+    // Read the standard input in a thread that lives until the program ends
+    fn stdin() -> TerminalInput {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            for byte in std::io::stdin().lock().bytes() {
+                if byte.ok().is_none_or(|byte| sender.send(byte).is_err()) {
+                    break;
+                }
+            }
+        });
+        TerminalInput::new(receiver)
+    }
+}
+
+impl Read for TerminalInput {
+    // Return one byte, waiting for it unless it is the rest of an escape sequence
+    // Return 0 bytes if the sequence does not go on or the input is closed
+    fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+        if buffer.is_empty() {
+            return Ok(0);
+        }
+        let byte = match self.sequence {
+            0 => self.bytes.recv().ok(),
+            _ => self.bytes.recv_timeout(ESCAPE_TIMEOUT).ok(),
+        };
+        let Some(byte) = byte else {
+            self.sequence = 0;
+            return Ok(0);
+        };
+        self.sequence = match byte {
+            ansi::ESCAPE => 2,
+            _ => self.sequence.saturating_sub(1),
+        };
+        buffer[0] = byte;
+        Ok(1)
+    }
+}
+
+// Clear the terminal and draw the screen with a message under it
+fn draw(screen: &str, message: &str) {
+    print!(
+        "{}{}{screen}\r\n{message}\r\n",
+        ansi::CLEAR_SCREEN,
+        ansi::HIDE_CURSOR
+    );
+    let _ = std::io::Write::flush(&mut std::io::stdout());
+}
+
+// Let the user move a cursor over the maze to choose the starting bomb
+// Return None if the user quits
+fn choose_start_bomb(game: &Bomberman, input: &mut impl std::io::Read) -> Option<Point> {
+    let bombs = game.bomb_positions();
+    let mut cursor = bombs.first().copied().unwrap_or(Point::new(0, 0));
+    let mut message = "Move with the arrows, Enter explodes the selected bomb, q quits";
+    loop {
+        draw(&game.to_ansi(Some(cursor), &[]), message);
+        match ansi::read_key(input).ok()? {
+            Key::Up => cursor.y = cursor.y.saturating_sub(1),
+            Key::Down => cursor.y = (cursor.y + 1).min(game.height().saturating_sub(1)),
+            Key::Left => cursor.x = cursor.x.saturating_sub(1),
+            Key::Right => cursor.x = (cursor.x + 1).min(game.width().saturating_sub(1)),
+            Key::Select if bombs.contains(&cursor) => return Some(cursor),
+            Key::Select => message = "There is no bomb in the selected square",
+            Key::Quit => return None,
+            Key::Other => (),
+        }
+    }
+}
+
+// Play the chain reaction one turn at a time, highlighting the squares hit in each turn
+// Every turn waits for the delay or, without one, for a key. q quits
+fn play_turns(
+    game: &Bomberman,
+    start_bomb: Point,
+    delay: Option<Duration>,
+    input: &mut impl std::io::Read,
+) {
+    let Ok(mut simulation) = Simulation::new(game.clone(), start_bomb) else {
+        return;
+    };
+    let wait = |input: &mut _| match delay {
+        Some(delay) => {
+            std::thread::sleep(delay);
+            true
+        }
+        None => !matches!(ansi::read_key(input), Ok(Key::Quit) | Err(_)),
+    };
+    while let Some(step) = simulation.step() {
+        let blast = svg::blast_squares(&step.events);
+        let message = format!(
+            "Turn {}: {} bomb(s) exploded, {} enemies alive",
+            step.turn,
+            step.bombs.len(),
+            simulation.game().enemies_alive()
+        );
+        draw(&simulation.game().to_ansi(None, &blast), &message);
+        if !wait(input) {
+            return;
+        }
+    }
+    let message = format!(
+        "Finished in turn {}, {} enemies alive. Press any key to exit",
+        simulation.turn(),
+        simulation.game().enemies_alive()
+    );
+    draw(&simulation.game().to_ansi(None, &[]), &message);
+    let _ = ansi::read_key(input);
+}
+
+// Show the maze in the terminal, choose the starting bomb with the cursor and play
+// the chain reaction turn by turn
+pub fn run(args: &[String]) -> Result<(), CliError> {
+    let flags = Flags::parse(args, &[INPUT, DELAY], &[])?;
+    let delay = match flags.value(DELAY) {
+        None => None,
+        Some(delay) => match delay.parse::<u64>() {
+            Ok(delay) => Some(Duration::from_millis(delay)),
+            Err(_) => {
+                return Err(InputError::InvalidInput(format!(
+                    "invalid delay {delay}, it should be a number of milliseconds"
+                ))
+                .into())
+            }
+        },
+    };
+    let (game, _) = load_game(&flags)?;
+
+    let _raw_mode = RawMode::enable()?;
+    let mut input = TerminalInput::stdin();
+    if let Some(start_bomb) = choose_start_bomb(&game, &mut input) {
+        play_turns(&game, start_bomb, delay, &mut input);
+    }
+    print!("{}", ansi::CLEAR_SCREEN);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_lone_escape_does_not_wait_for_more_keys() {
+        let (sender, receiver) = mpsc::channel();
+        let mut input = TerminalInput::new(receiver);
+        for byte in b"\x1b[A\x1b" {
+            sender.send(*byte).unwrap();
+        }
+        assert_eq!(ansi::read_key(&mut input).ok(), Some(Key::Up));
+        // The sender is still open, the Esc is not followed by anything
+        assert_eq!(ansi::read_key(&mut input).ok(), Some(Key::Quit));
+        sender.send(b'j').unwrap();
+        assert_eq!(ansi::read_key(&mut input).ok(), Some(Key::Down));
+    }
+}
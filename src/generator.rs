@@ -0,0 +1,229 @@
+use crate::bomberman::Bomberman;
+use crate::solver;
+use crate::utils::point::Point;
+
+// Number of mazes tried before giving up on a clearable one
+pub const MAX_ATTEMPTS: u32 = 1000;
+
+// Options of a generated maze
+// Each density is the chance of a square to hold that object, they should add up to 1 at most
+// With classic_walls every square with odd x and y is a wall, like the assignment mazes
+// With clearable the maze is generated again until some starting bomb kills every enemy
+#[derive(Debug, PartialEq, Clone)]
+pub struct GeneratorOptions {
+    pub width: u32,
+    pub height: u32,
+    pub walls: f64,
+    pub rocks: f64,
+    pub bombs: f64,
+    pub enemies: f64,
+    pub redirections: f64,
+    pub max_range: u32,
+    pub max_health: u32,
+    pub seed: u64,
+    pub classic_walls: bool,
+    pub clearable: bool,
+}
+
+impl Default for GeneratorOptions {
+    fn default() -> GeneratorOptions {
+        GeneratorOptions {
+            width: 7,
+            height: 7,
+            walls: 0.05,
+            rocks: 0.1,
+            bombs: 0.15,
+            enemies: 0.1,
+            redirections: 0.05,
+            max_range: 4,
+            max_health: 3,
+            seed: 1,
+            classic_walls: false,
+            clearable: false,
+        }
+    }
+}
+
+// Xorshift64* pseudo random generator, the same seed always gives the same numbers
+struct Random(u64);
+
+impl Random {
+    fn new(seed: u64) -> Random {
+        // Xorshift gets stuck at 0
+        Random(seed.max(1))
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    // Return a number in [0, 1)
+    fn next_f64(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // Return a number in [1, max]
+    fn next_in(&mut self, max: u32) -> u32 {
+        (self.next() % max.max(1) as u64) as u32 + 1
+    }
+}
+
+// Generate a maze with the options
+// Returns the maze and, if it has to be clearable, the starting bomb that clears it
+// Returns an error if the options are invalid or no clearable maze was found
+pub fn generate(options: &GeneratorOptions) -> Result<(Bomberman, Option<Point>), String> {
+    if options.width == 0 || options.height == 0 {
+        return Err("The maze should have at least one row and one column".to_string());
+    }
+    if options.max_range == 0 || !(1..=3).contains(&options.max_health) {
+        return Err(
+            "The bomb range should be at least 1 and the enemy health between 1 and 3".to_string(),
+        );
+    }
+    let densities = densities(options);
+    if densities
+        .iter()
+        .any(|density| !(0.0..=1.0).contains(density))
+        || densities.iter().sum::<f64>() > 1.0
+    {
+        return Err("The densities should be between 0 and 1 and add up to 1 at most".to_string());
+    }
+
+    let mut random = Random::new(options.seed);
+    if !options.clearable {
+        return Ok((maze(options, &mut random)?, None));
+    }
+    for _ in 0..MAX_ATTEMPTS {
+        let game = maze(options, &mut random)?;
+        let clear_from = solver::solve(&game)
+            .into_iter()
+            .find(|solution| solution.remaining_enemies == 0)
+            .map(|solution| solution.start_bomb);
+        if let Some(start_bomb) = clear_from {
+            return Ok((game, Some(start_bomb)));
+        }
+    }
+    Err(format!(
+        "No clearable maze found in {MAX_ATTEMPTS} attempts, try with more bombs or less walls"
+    ))
+}
+
+fn maze(options: &GeneratorOptions, random: &mut Random) -> Result<Bomberman, String> {
+    let mut lines = Vec::new();
+    for y in 0..options.height {
+        let line: Vec<String> = (0..options.width)
+            .map(|x| {
+                if options.classic_walls && x % 2 == 1 && y % 2 == 1 {
+                    return "W".to_string();
+                }
+                square(options, random)
+            })
+            .collect();
+        lines.push(line.join(" "));
+    }
+    Bomberman::new(&lines.join("\n")).map_err(|e| e.to_string())
+}
+
+// Return the densities of walls, rocks, bombs, enemies and redirections, in that order
+fn densities(options: &GeneratorOptions) -> [f64; 5] {
+    [
+        options.walls,
+        options.rocks,
+        options.bombs,
+        options.enemies,
+        options.redirections,
+    ]
+}
+
+// Pick the token of a square according to the densities
+fn square(options: &GeneratorOptions, random: &mut Random) -> String {
+    let mut roll = random.next_f64();
+    let index = densities(options).iter().position(|density| {
+        roll -= density;
+        roll < 0.0
+    });
+    match index {
+        Some(0) => "W".to_string(),
+        Some(1) => "R".to_string(),
+        Some(2) => {
+            // One in four bombs is penetrating
+            let bomb_type = if random.next_in(4) == 1 { "S" } else { "B" };
+            format!("{bomb_type}{}", random.next_in(options.max_range))
+        }
+        Some(3) => format!("F{}", random.next_in(options.max_health)),
+        Some(4) => ["DU", "DD", "DL", "DR"][random.next_in(4) as usize - 1].to_string(),
+        _ => "_".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_generates_same_maze() {
+        let options = GeneratorOptions {
+            seed: 42,
+            ..GeneratorOptions::default()
+        };
+        let (first, _) = generate(&options).unwrap();
+        let (second, _) = generate(&options).unwrap();
+        assert_eq!(first.to_string(), second.to_string());
+        let other = GeneratorOptions {
+            seed: 43,
+            ..options
+        };
+        assert_ne!(first.to_string(), generate(&other).unwrap().0.to_string());
+    }
+
+    #[test]
+    fn test_generate_has_the_size() {
+        let options = GeneratorOptions {
+            width: 5,
+            height: 3,
+            ..GeneratorOptions::default()
+        };
+        let (game, _) = generate(&options).unwrap();
+        assert_eq!((game.width(), game.height()), (5, 3));
+    }
+
+    #[test]
+    fn test_classic_walls_on_odd_squares() {
+        let options = GeneratorOptions {
+            walls: 0.0,
+            classic_walls: true,
+            ..GeneratorOptions::default()
+        };
+        let (game, _) = generate(&options).unwrap();
+        for (y, line) in game.to_string().lines().enumerate() {
+            for (x, square) in line.split(' ').enumerate() {
+                assert_eq!(square == "W", x % 2 == 1 && y % 2 == 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_clearable_maze_is_cleared_from_its_start() {
+        let options = GeneratorOptions {
+            clearable: true,
+            seed: 7,
+            ..GeneratorOptions::default()
+        };
+        let (game, start) = generate(&options).unwrap();
+        let outcome = game.simulate(start.unwrap()).unwrap();
+        assert_eq!(outcome.remaining_enemies, 0);
+    }
+
+    #[test]
+    fn test_densities_over_one_is_error() {
+        let options = GeneratorOptions {
+            walls: 0.5,
+            rocks: 0.6,
+            ..GeneratorOptions::default()
+        };
+        assert!(generate(&options).is_err());
+    }
+}
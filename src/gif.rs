@@ -0,0 +1,272 @@
+use std::collections::HashMap;
+
+// Largest code of the GIF variant of LZW, codes are at most 12 bits
+const MAX_CODE: u16 = 4095;
+
+// Encode the frames as an animated GIF that loops forever
+// Every frame is a width x height list of indexes into the palette, row by row
+// The palette has at most 256 colors, delay is the time of each frame in hundredths of a second
+pub fn encode(
+    width: u16,
+    height: u16,
+    palette: &[[u8; 3]],
+    frames: &[Vec<u8>],
+    delay: u16,
+) -> Vec<u8> {
+    let mut encoder = Encoder::new(width, height, palette, delay);
+    for frame in frames {
+        encoder.add_frame(frame);
+    }
+    encoder.finish()
+}
+
+// Animated GIF that loops forever, built one frame at a time so only the compressed
+// frames are kept in memory
+pub struct Encoder {
+    gif: Vec<u8>,
+    width: u16,
+    height: u16,
+    delay: u16,
+    min_code_size: u8,
+}
+
+impl Encoder {
+    // Start a GIF of width x height pixels with the palette and the delay of every frame
+    // The palette has at most 256 colors, delay is the time of each frame in hundredths of a second
+    pub fn new(width: u16, height: u16, palette: &[[u8; 3]], delay: u16) -> Encoder {
+        // The color table size is a power of 2, at least 2 colors
+        let table_bits = (palette.len().max(2) as f64).log2().ceil() as u8;
+        let mut gif = b"GIF89a".to_vec();
+        gif.extend_from_slice(&width.to_le_bytes());
+        gif.extend_from_slice(&height.to_le_bytes());
+        gif.push(0x80 | ((table_bits - 1) << 4) | (table_bits - 1));
+        gif.extend_from_slice(&[0, 0]);
+        for index in 0..1usize << table_bits {
+            gif.extend_from_slice(&palette.get(index).copied().unwrap_or([0, 0, 0]));
+        }
+
+        // Application extension to loop the animation forever
+        gif.extend_from_slice(&[0x21, 0xFF, 0x0B]);
+        gif.extend_from_slice(b"NETSCAPE2.0");
+        gif.extend_from_slice(&[0x03, 0x01, 0x00, 0x00, 0x00]);
+
+        Encoder {
+            gif,
+            width,
+            height,
+            delay,
+            min_code_size: table_bits.max(2),
+        }
+    }
+
+    // Add a frame, a width x height list of indexes into the palette, row by row
+    pub fn add_frame(&mut self, frame: &[u8]) {
+        let gif = &mut self.gif;
+        // Graphic control extension with the delay of the frame
+        gif.extend_from_slice(&[0x21, 0xF9, 0x04, 0x00]);
+        gif.extend_from_slice(&self.delay.to_le_bytes());
+        gif.extend_from_slice(&[0x00, 0x00]);
+
+        gif.push(0x2C);
+        gif.extend_from_slice(&[0, 0, 0, 0]);
+        gif.extend_from_slice(&self.width.to_le_bytes());
+        gif.extend_from_slice(&self.height.to_le_bytes());
+        gif.push(0x00);
+
+        gif.push(self.min_code_size);
+        for block in compress(self.min_code_size, frame).chunks(255) {
+            gif.push(block.len() as u8);
+            gif.extend_from_slice(block);
+        }
+        gif.push(0x00);
+    }
+
+    // Return the GIF with every frame added
+    pub fn finish(mut self) -> Vec<u8> {
+        self.gif.push(0x3B);
+        self.gif
+    }
+}
+
+// Write codes of variable size, least significant bit first
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u8) {
+        self.buffer |= (code as u32) << self.bits;
+        self.bits += size;
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+// Compress the pixels with the GIF variant of LZW
+// The code size grows with the table and the table is cleared when it is full
+fn compress(min_code_size: u8, pixels: &[u8]) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+    let mut writer = BitWriter::default();
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut code_size = min_code_size + 1;
+    let mut next_code = end + 1;
+
+    writer.write(clear, code_size);
+    let Some((&first, pixels)) = pixels.split_first() else {
+        writer.write(end, code_size);
+        return writer.finish();
+    };
+    let mut prefix = first as u16;
+    for &pixel in pixels {
+        if let Some(&code) = table.get(&(prefix, pixel)) {
+            prefix = code;
+            continue;
+        }
+        writer.write(prefix, code_size);
+        table.insert((prefix, pixel), next_code);
+        if next_code == 1 << code_size {
+            code_size += 1;
+        }
+        next_code += 1;
+        if next_code > MAX_CODE {
+            writer.write(clear, code_size);
+            table.clear();
+            code_size = min_code_size + 1;
+            next_code = end + 1;
+        }
+        prefix = pixel as u16;
+    }
+    writer.write(prefix, code_size);
+    writer.write(end, code_size);
+    writer.finish()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Decode the LZW data of a frame following the GIF specification
+    fn decompress(min_code_size: u8, data: &[u8]) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let end = clear + 1;
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let mut code_size = min_code_size + 1;
+        let mut previous: Option<usize> = None;
+        let mut pixels = Vec::new();
+        let (mut buffer, mut bits, mut bytes) = (0u32, 0u8, data.iter());
+        loop {
+            while bits < code_size {
+                buffer |= (*bytes.next().unwrap() as u32) << bits;
+                bits += 8;
+            }
+            let code = (buffer & ((1 << code_size) - 1)) as usize;
+            buffer >>= code_size;
+            bits -= code_size;
+
+            if code == clear {
+                table = (0..clear).map(|i| vec![i as u8]).collect();
+                table.extend([Vec::new(), Vec::new()]);
+                code_size = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == end {
+                return pixels;
+            }
+            let entry = match previous {
+                None => table[code].clone(),
+                Some(previous) => {
+                    let entry = if code < table.len() {
+                        table[code].clone()
+                    } else {
+                        let mut entry = table[previous].clone();
+                        entry.push(table[previous][0]);
+                        entry
+                    };
+                    let mut new_entry = table[previous].clone();
+                    new_entry.push(entry[0]);
+                    table.push(new_entry);
+                    if table.len() == 1 << code_size && code_size < 12 {
+                        code_size += 1;
+                    }
+                    entry
+                }
+            };
+            pixels.extend_from_slice(&entry);
+            previous = Some(code);
+        }
+    }
+
+    // Return the pixels of every frame of the GIF
+    fn decode_frames(gif: &[u8]) -> Vec<Vec<u8>> {
+        let table_size = 3 * (1 << ((gif[10] & 0x07) + 1));
+        let mut position = 13 + table_size;
+        let mut frames = Vec::new();
+        while gif[position] != 0x3B {
+            let is_image = gif[position] == 0x2C;
+            position += if is_image { 10 } else { 2 };
+            let min_code_size = gif[position];
+            if is_image {
+                position += 1;
+            }
+            let mut data = Vec::new();
+            while gif[position] != 0 {
+                let size = gif[position] as usize;
+                data.extend_from_slice(&gif[position + 1..position + 1 + size]);
+                position += size + 1;
+            }
+            position += 1;
+            if is_image {
+                frames.push(decompress(min_code_size, &data));
+            }
+        }
+        frames
+    }
+
+    #[test]
+    fn test_encode_writes_header_and_palette() {
+        let gif = encode(2, 1, &[[255, 0, 0], [0, 0, 255]], &[vec![0, 1]], 10);
+        assert_eq!(&gif[..6], b"GIF89a");
+        assert_eq!(&gif[6..10], &[2, 0, 1, 0]);
+        assert_eq!(&gif[13..19], &[255, 0, 0, 0, 0, 255]);
+        assert_eq!(gif.last(), Some(&0x3B));
+    }
+
+    #[test]
+    fn test_encoded_frames_decode_to_the_same_pixels() {
+        let palette = vec![[0, 0, 0]; 11];
+        let first: Vec<u8> = (0..64 * 64).map(|i| ((i / 7) % 11) as u8).collect();
+        let second = vec![3; 64 * 64];
+        let gif = encode(64, 64, &palette, &[first.clone(), second.clone()], 20);
+        assert_eq!(decode_frames(&gif), vec![first, second]);
+    }
+
+    #[test]
+    fn test_table_is_cleared_when_full() {
+        // Noisy pixels fill the table many times
+        let mut seed = 7u32;
+        let pixels: Vec<u8> = (0..100_000)
+            .map(|_| {
+                seed ^= seed << 13;
+                seed ^= seed >> 17;
+                seed ^= seed << 5;
+                (seed % 16) as u8
+            })
+            .collect();
+        assert_eq!(decompress(4, &compress(4, &pixels)), pixels);
+    }
+}
@@ -1,7 +1,21 @@
+pub mod ansi;
 pub mod bomberman;
 pub mod maze_placeable;
 pub mod utils;
 
 pub mod bomberman_errors;
+pub mod bomberman_events;
 
 pub mod input_errors;
+pub mod maze_header;
+pub mod maze_json;
+pub mod outcome;
+
+pub mod animation;
+pub mod board_diff;
+pub mod generator;
+pub mod gif;
+pub mod lint;
+pub mod simulation;
+pub mod solver;
+pub mod svg;
@@ -0,0 +1,315 @@
+use crate::bomberman::Bomberman;
+use crate::maze_placeable::bomb_type::BombType;
+use crate::maze_placeable::obstacle::Obstacle;
+use crate::utils::direction::Direction;
+use crate::utils::point::Point;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+// Something legal but suspicious in a maze
+#[derive(Debug, PartialEq, Clone)]
+pub enum Warning {
+    // A bomb no other bomb reaches, it only explodes as a starting bomb
+    UnreachableBomb(Point),
+    // An enemy no bomb reaches
+    UnreachableEnemy(Point),
+    // A redirection that sends the explosion into a wall or out of the maze
+    RedirectionIntoWall {
+        position: Point,
+        direction: Direction,
+    },
+    // Redirections that send the explosion around in a loop
+    RedirectionLoop(Vec<Point>),
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Warning::UnreachableBomb(position) => write!(
+                f,
+                "UnreachableBomb: The bomb at {position} is not reached by any other bomb, it only explodes as a starting bomb"
+            ),
+            Warning::UnreachableEnemy(position) => write!(
+                f,
+                "UnreachableEnemy: The enemy at {position} is not reached by any bomb"
+            ),
+            Warning::RedirectionIntoWall {
+                position,
+                direction,
+            } => write!(
+                f,
+                "RedirectionIntoWall: The redirection at {position} sends the explosion {direction:?} into a wall or out of the maze"
+            ),
+            Warning::RedirectionLoop(positions) => {
+                let positions: Vec<String> = positions.iter().map(Point::to_string).collect();
+                write!(
+                    f,
+                    "RedirectionLoop: The redirections at {} send the explosion around in a loop",
+                    positions.join(", ")
+                )
+            }
+        }
+    }
+}
+
+// Return every warning of the maze, bombs and enemies first, then redirections,
+// each group in the order of its positions
+pub fn lint(game: &Bomberman) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+    let reach = game.explosion_reach();
+
+    let mut bombs: Vec<Point> = reach
+        .iter()
+        .filter(|(bomb, _)| {
+            !reach
+                .iter()
+                .any(|(other, points)| other != bomb && points.contains(bomb))
+        })
+        .map(|(bomb, _)| *bomb)
+        .collect();
+    bombs.sort();
+    warnings.extend(bombs.into_iter().map(Warning::UnreachableBomb));
+
+    let reached: HashSet<Point> = reach.into_iter().flat_map(|(_, points)| points).collect();
+    let mut enemies: Vec<Point> = game
+        .enemy_positions()
+        .into_iter()
+        .filter(|enemy| !reached.contains(enemy))
+        .collect();
+    enemies.sort();
+    warnings.extend(enemies.into_iter().map(Warning::UnreachableEnemy));
+
+    warnings.extend(redirections_into_walls(game));
+    warnings.extend(redirection_loops(game));
+    warnings
+}
+
+// Return the directions the obstacle sends an explosion to, those different from
+// the direction the explosion comes from
+fn redirected_directions(obstacle: &Obstacle) -> Vec<(Direction, Direction)> {
+    Direction::iter()
+        .flat_map(|from| {
+            obstacle
+                .next_directions(from)
+                .into_iter()
+                .map(move |to| (from, to))
+        })
+        .filter(|(from, to)| from != to)
+        .collect()
+}
+
+// Return the obstacle at the position, if any
+fn obstacle_at(game: &Bomberman, position: Point) -> Option<&Obstacle> {
+    game.grid()
+        .obstacle_at(position)
+        .map(|index| &game.obstacles()[index])
+}
+
+// Return the position of the other teleporter if there is a paired teleporter in the position
+fn partner_at(game: &Bomberman, position: Point) -> Option<Point> {
+    game.grid()
+        .obstacle_at(position)
+        .and_then(|index| game.grid().partner(index))
+        .map(|index| game.obstacles()[index].position)
+}
+
+// Return the next square in the direction if an explosion can get into it
+// Only walls stop every explosion, rocks let penetrating bombs pass
+fn next_open_square(game: &Bomberman, position: Point, direction: Direction) -> Option<Point> {
+    let next = position
+        .next_point(direction, game.width(), game.height())
+        .ok()?;
+    match obstacle_at(game, next) {
+        Some(obstacle) if !obstacle.explosion_can_pass(BombType::Penetrating) => None,
+        _ => Some(next),
+    }
+}
+
+fn redirections_into_walls(game: &Bomberman) -> Vec<Warning> {
+    let mut blocked = Vec::new();
+    for obstacle in game.obstacles() {
+        let mut directions: Vec<Direction> = redirected_directions(obstacle)
+            .into_iter()
+            .map(|(_, to)| to)
+            .collect();
+        directions.dedup();
+        blocked.extend(
+            directions
+                .into_iter()
+                .filter(|direction| next_open_square(game, obstacle.position, *direction).is_none())
+                .map(|direction| (obstacle.position, direction)),
+        );
+    }
+    blocked.sort_by_key(|(position, _)| *position);
+    blocked
+        .into_iter()
+        .map(|(position, direction)| Warning::RedirectionIntoWall {
+            position,
+            direction,
+        })
+        .collect()
+}
+
+// Follow the explosion from each redirection, ignoring its distance, until it stops or
+// gets back to a square it already left in the same direction
+// The explosion jumps between teleporters and is not followed after a splitter
+// Each loop is reported once with the redirections in it
+fn redirection_loops(game: &Bomberman) -> Vec<Warning> {
+    let mut loops: Vec<Vec<Point>> = Vec::new();
+    for obstacle in game.obstacles() {
+        // Explosions leave a teleporter from its partner
+        let starts: Vec<(Point, Direction)> = match partner_at(game, obstacle.position) {
+            Some(partner) => Direction::iter().map(|d| (partner, d)).collect(),
+            None => redirected_directions(obstacle)
+                .into_iter()
+                .map(|(_, to)| (obstacle.position, to))
+                .collect(),
+        };
+        for start in starts {
+            let mut visited: HashMap<(Point, Direction), usize> = HashMap::new();
+            let mut path = Vec::new();
+            let mut state = Some(start);
+            while let Some(current) = state.filter(|state| !visited.contains_key(state)) {
+                visited.insert(current, path.len());
+                path.push(current);
+                let (position, direction) = current;
+                state = next_open_square(game, position, direction).and_then(|next| {
+                    let Some(obstacle) = obstacle_at(game, next) else {
+                        return Some((next, direction));
+                    };
+                    // A teleporter sends the explosion on from its partner
+                    if let Some(partner) = partner_at(game, next) {
+                        return Some((partner, direction));
+                    }
+                    match obstacle.next_directions(direction)[..] {
+                        [next_direction] => Some((next, next_direction)),
+                        _ => None,
+                    }
+                });
+            }
+            // The explosion stopped without getting back to a square
+            let Some(start) = state.and_then(|state| visited.get(&state)) else {
+                continue;
+            };
+            // The path only has the teleporters the explosion left from, the one it
+            // got into is their partner, so both are part of the loop
+            let mut redirections: Vec<Point> = path[*start..]
+                .iter()
+                .flat_map(|(position, _)| match partner_at(game, *position) {
+                    Some(partner) => vec![*position, partner],
+                    None => vec![*position],
+                })
+                .filter(|position| {
+                    obstacle_at(game, *position).is_some_and(|o| {
+                        !redirected_directions(o).is_empty()
+                            || partner_at(game, *position).is_some()
+                    })
+                })
+                .collect();
+            redirections.sort();
+            redirections.dedup();
+            if !loops.contains(&redirections) {
+                loops.push(redirections);
+            }
+        }
+    }
+    loops.sort();
+    loops.into_iter().map(Warning::RedirectionLoop).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn lint_maze(maze: &str) -> Vec<Warning> {
+        lint(&Bomberman::new(maze).unwrap())
+    }
+
+    #[test]
+    fn test_clean_maze_has_no_warnings() {
+        assert_eq!(lint_maze("B2 _ F1\n_ W _\nB2 _ _\n"), vec![]);
+    }
+
+    #[test]
+    fn test_unreachable_bomb_and_enemy() {
+        let warnings = lint_maze("B1 _ _\n_ W _\n_ _ F1\n");
+        assert_eq!(
+            warnings,
+            vec![
+                Warning::UnreachableBomb(Point::new(0, 0)),
+                Warning::UnreachableEnemy(Point::new(2, 2))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_enemy_behind_wall_is_unreachable() {
+        let warnings = lint_maze("B3 W F1\nB1 _ _\n");
+        assert!(warnings.contains(&Warning::UnreachableEnemy(Point::new(2, 0))));
+    }
+
+    #[test]
+    fn test_redirection_into_wall_and_edge() {
+        let warnings = lint_maze("B2 DU _\n_ W _\n_ DR B1\n");
+        assert!(warnings.contains(&Warning::RedirectionIntoWall {
+            position: Point::new(1, 0),
+            direction: Direction::Up
+        }));
+        assert!(!warnings.contains(&Warning::RedirectionIntoWall {
+            position: Point::new(1, 2),
+            direction: Direction::Right
+        }));
+    }
+
+    #[test]
+    fn test_redirection_loop_is_reported_once() {
+        let warnings = lint_maze("B1 DR DL\n_ _ _\n");
+        let loops: Vec<&Warning> = warnings
+            .iter()
+            .filter(|w| matches!(w, Warning::RedirectionLoop(_)))
+            .collect();
+        assert_eq!(
+            loops,
+            vec![&Warning::RedirectionLoop(vec![
+                Point::new(1, 0),
+                Point::new(2, 0)
+            ])]
+        );
+    }
+
+    #[test]
+    fn test_display_warning() {
+        assert_eq!(
+            Warning::UnreachableEnemy(Point::new(4, 2)).to_string(),
+            "UnreachableEnemy: The enemy at (4, 2) is not reached by any bomb"
+        );
+    }
+
+    #[test]
+    fn test_splitter_into_wall_and_no_loop_through_it() {
+        let warnings = lint_maze("B1 _ _\nDR + DL\n_ W _\n");
+        assert!(warnings.contains(&Warning::RedirectionIntoWall {
+            position: Point::new(1, 1),
+            direction: Direction::Down
+        }));
+        assert!(!warnings
+            .iter()
+            .any(|w| matches!(w, Warning::RedirectionLoop(_))));
+    }
+
+    #[test]
+    fn test_teleporter_loop_is_reported() {
+        let warnings = lint_maze("B1 T1 _ T1\n_ _ _ _\n");
+        let loops: Vec<&Warning> = warnings
+            .iter()
+            .filter(|w| matches!(w, Warning::RedirectionLoop(_)))
+            .collect();
+        assert_eq!(
+            loops,
+            vec![&Warning::RedirectionLoop(vec![
+                Point::new(1, 0),
+                Point::new(3, 0)
+            ])]
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use crate::input_errors::InputError;
+use crate::utils::point::Point;
+
+// Start of the header line of a maze with its starting points. Eg: # start: 0,0 2,4
+pub const START_HEADER: &str = "# start:";
+
+// Parse a point written as x,y with positive numbers
+pub fn parse_point(point: &str) -> Result<Point, InputError> {
+    let coordinates = point
+        .split_once(',')
+        .map(|(x, y)| (x.trim().parse::<u32>(), y.trim().parse::<u32>()));
+    match coordinates {
+        Some((Ok(x), Ok(y))) => Ok(Point::new(x, y)),
+        _ => Err(InputError::InvalidInput(format!(
+            "invalid starting point {point}, it should be x,y with positive numbers"
+        ))),
+    }
+}
+
+// Parse points separated by whitespace. Eg: "0,0 2,4"
+pub fn parse_points(points: &str) -> Result<Vec<Point>, InputError> {
+    points.split_whitespace().map(parse_point).collect()
+}
+
+// Split the header with the starting points from the maze, if it has one
+// Eg: "# start: 0,0\nB1 _\n" -> ([(0, 0)], "B1 _\n")
+pub fn split_header(contents: &str) -> Result<(Vec<Point>, &str), InputError> {
+    match contents.strip_prefix(START_HEADER) {
+        Some(rest) => {
+            let (header, maze) = rest.split_once('\n').unwrap_or((rest, ""));
+            Ok((parse_points(header)?, maze))
+        }
+        None => Ok((Vec::new(), contents)),
+    }
+}
+
+// Write the header with the starting points before the maze
+pub fn with_header(start_points: &[Point], maze: &str) -> String {
+    let points: Vec<String> = start_points
+        .iter()
+        .map(|point| format!("{},{}", point.x, point.y))
+        .collect();
+    format!("{START_HEADER} {}\n{maze}", points.join(" "))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_point() {
+        assert_eq!(parse_point("2, 4").ok(), Some(Point::new(2, 4)));
+        assert!(parse_point("2").is_err());
+        assert!(parse_point("-1,0").is_err());
+    }
+
+    #[test]
+    fn test_split_header() {
+        assert_eq!(
+            split_header("# start: 0,0\nB1 _\n"),
+            Ok((vec![Point::new(0, 0)], "B1 _\n"))
+        );
+        assert_eq!(split_header("B1 _\n"), Ok((vec![], "B1 _\n")));
+    }
+
+    #[test]
+    fn test_with_header_is_split_back() {
+        let points = vec![Point::new(0, 0), Point::new(2, 4)];
+        let contents = with_header(&points, "B1 _\n");
+        assert_eq!(contents, "# start: 0,0 2,4\nB1 _\n");
+        assert_eq!(split_header(&contents), Ok((points, "B1 _\n")));
+    }
+}
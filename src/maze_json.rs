@@ -0,0 +1,295 @@
+use crate::bomberman_errors::BombermanError;
+use crate::maze_placeable::bomb::{Bomb, FUSE_SEPARATOR};
+use crate::maze_placeable::bomb_type::{BombType, NORMAL_BOMB, PENETRATING_BOMB};
+use crate::maze_placeable::enemy::{Enemy, ENEMY};
+use crate::maze_placeable::obstacle::Obstacle;
+use crate::maze_placeable::obstacle_type::{ObstacleType, BREAKABLE, TELEPORTER};
+use crate::utils::json::JsonValue;
+use crate::utils::maze_display::MazeDisplay;
+use crate::utils::point::Point;
+
+// JSON representation of a maze:
+// {
+//   "size": {"width": 3, "height": 2},
+//   "bombs": [{"type": "Normal", "range": 2, "position": {"x": 0, "y": 0}}],
+//   "enemies": [{"health": 1, "position": {"x": 2, "y": 0}}],
+//   "obstacles": [{"type": "Wall", "position": {"x": 1, "y": 1}}]
+// }
+// Bombs with a fuse also have a "fuse" field, breakable obstacles a "hits" field
+// and teleporters an "id" field
+
+// Largest number of squares a maze read from JSON can declare, the board of the maze
+// is allocated from its size before any square is read
+const MAX_SQUARES: u32 = 1_000_000;
+
+// Squares of a maze read from JSON, each one as its maze token and position
+#[derive(Debug, PartialEq)]
+pub struct JsonMaze {
+    pub width: u32,
+    pub height: u32,
+    pub squares: Vec<(String, Point)>,
+}
+
+// Write the maze as JSON, only the bombs and enemies given are listed
+pub fn to_json(
+    width: u32,
+    height: u32,
+    bombs: &[&Bomb],
+    enemies: &[&Enemy],
+    obstacles: &[&Obstacle],
+) -> String {
+    let size = JsonValue::Object(vec![
+        ("width".to_string(), number(width)),
+        ("height".to_string(), number(height)),
+    ]);
+    let bombs = bombs.iter().map(|bomb| bomb_to_json(bomb)).collect();
+    let enemies = enemies
+        .iter()
+        .map(|enemy| {
+            JsonValue::Object(vec![
+                ("health".to_string(), number(enemy.health())),
+                ("position".to_string(), position(enemy.get_position())),
+            ])
+        })
+        .collect();
+    let obstacles = obstacles
+        .iter()
+        .map(|obstacle| {
+            let mut fields = vec![(
+                "type".to_string(),
+                JsonValue::String(obstacle.obstacle_type.name().to_string()),
+            )];
+            match obstacle.obstacle_type {
+                ObstacleType::Breakable(hits) => fields.push(("hits".to_string(), number(hits))),
+                ObstacleType::Teleporter(id) => fields.push(("id".to_string(), number(id))),
+                _ => (),
+            }
+            fields.push(("position".to_string(), position(obstacle.position)));
+            JsonValue::Object(fields)
+        })
+        .collect();
+    let maze = JsonValue::Object(vec![
+        ("size".to_string(), size),
+        ("bombs".to_string(), JsonValue::Array(bombs)),
+        ("enemies".to_string(), JsonValue::Array(enemies)),
+        ("obstacles".to_string(), JsonValue::Array(obstacles)),
+    ]);
+    maze.to_pretty_string() + "\n"
+}
+
+// Read a maze from JSON
+// Every bomb, enemy and obstacle is converted to its maze token so it is validated
+// like a square of a maze file. Eg: {"type": "Normal", "range": 3, "fuse": 2} -> B3:2
+// Return an error if the JSON is invalid or does not describe a maze
+pub fn from_json(json: &str) -> Result<JsonMaze, BombermanError> {
+    let maze = JsonValue::parse(json).map_err(BombermanError::InvalidJson)?;
+    let size = field(&maze, "size", "maze")?;
+    let width = u32_field(size, "width", "size")?;
+    let height = u32_field(size, "height", "size")?;
+    if width
+        .checked_mul(height)
+        .is_none_or(|squares| squares > MAX_SQUARES)
+    {
+        return Err(invalid(&format!(
+            "maze size {width}x{height} has more than {MAX_SQUARES} squares"
+        )));
+    }
+
+    let mut squares = Vec::new();
+    for bomb in array_field(&maze, "bombs")? {
+        let prefix = match str_field(bomb, "type", "bomb")? {
+            "Normal" => NORMAL_BOMB,
+            "Penetrating" => PENETRATING_BOMB,
+            other => return Err(invalid(&format!("invalid bomb type {other}"))),
+        };
+        let mut token = format!("{prefix}{}", u32_field(bomb, "range", "bomb")?);
+        if bomb.get("fuse").is_some() {
+            token.push_str(&format!(
+                "{FUSE_SEPARATOR}{}",
+                u32_field(bomb, "fuse", "bomb")?
+            ));
+        }
+        squares.push((token, position_field(bomb)?));
+    }
+    for enemy in array_field(&maze, "enemies")? {
+        let token = format!("{ENEMY}{}", u32_field(enemy, "health", "enemy")?);
+        squares.push((token, position_field(enemy)?));
+    }
+    for obstacle in array_field(&maze, "obstacles")? {
+        let obstacle_type = ObstacleType::from_name(str_field(obstacle, "type", "obstacle")?)
+            .map_err(|reason| invalid(&reason))?;
+        let token = match obstacle_type {
+            ObstacleType::Breakable(_) if obstacle.get("hits").is_some() => {
+                format!("{BREAKABLE}{}", u32_field(obstacle, "hits", "obstacle")?)
+            }
+            ObstacleType::Teleporter(_) => {
+                format!("{TELEPORTER}{}", u32_field(obstacle, "id", "obstacle")?)
+            }
+            _ => obstacle_type.to_string(),
+        };
+        squares.push((token, position_field(obstacle)?));
+    }
+
+    Ok(JsonMaze {
+        width,
+        height,
+        squares,
+    })
+}
+
+fn bomb_to_json(bomb: &Bomb) -> JsonValue {
+    let bomb_type = match bomb.bomb_type() {
+        BombType::Normal => "Normal",
+        BombType::Penetrating => "Penetrating",
+    };
+    let mut fields = vec![
+        ("type".to_string(), JsonValue::String(bomb_type.to_string())),
+        ("range".to_string(), number(bomb.explosion_distance())),
+    ];
+    if let Some(fuse) = bomb.fuse() {
+        fields.push(("fuse".to_string(), number(fuse)));
+    }
+    fields.push(("position".to_string(), position(bomb.get_position())));
+    JsonValue::Object(fields)
+}
+
+fn number(n: u32) -> JsonValue {
+    JsonValue::Number(n as f64)
+}
+
+fn position(point: Point) -> JsonValue {
+    JsonValue::Object(vec![
+        ("x".to_string(), number(point.x)),
+        ("y".to_string(), number(point.y)),
+    ])
+}
+
+fn invalid(reason: &str) -> BombermanError {
+    BombermanError::InvalidJson(reason.to_string())
+}
+
+fn field<'a>(
+    value: &'a JsonValue,
+    key: &str,
+    owner: &str,
+) -> Result<&'a JsonValue, BombermanError> {
+    value
+        .get(key)
+        .ok_or_else(|| invalid(&format!("{owner} is missing the field {key}")))
+}
+
+fn u32_field(value: &JsonValue, key: &str, owner: &str) -> Result<u32, BombermanError> {
+    field(value, key, owner)?
+        .as_u32()
+        .ok_or_else(|| invalid(&format!("{owner} field {key} should be a positive integer")))
+}
+
+fn str_field<'a>(value: &'a JsonValue, key: &str, owner: &str) -> Result<&'a str, BombermanError> {
+    field(value, key, owner)?
+        .as_str()
+        .ok_or_else(|| invalid(&format!("{owner} field {key} should be a string")))
+}
+
+// Missing lists are read as empty, so a maze without enemies can leave them out
+fn array_field<'a>(value: &'a JsonValue, key: &str) -> Result<&'a [JsonValue], BombermanError> {
+    match value.get(key) {
+        None => Ok(&[]),
+        Some(list) => list
+            .as_array()
+            .ok_or_else(|| invalid(&format!("maze field {key} should be a list"))),
+    }
+}
+
+fn position_field(value: &JsonValue) -> Result<Point, BombermanError> {
+    let position = field(value, "position", "square")?;
+    Ok(Point::new(
+        u32_field(position, "x", "position")?,
+        u32_field(position, "y", "position")?,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_json_converts_squares_to_tokens() {
+        let json = r#"{
+            "size": {"width": 3, "height": 2},
+            "bombs": [{"type": "Penetrating", "range": 3, "fuse": 2, "position": {"x": 0, "y": 0}}],
+            "enemies": [{"health": 2, "position": {"x": 2, "y": 0}}],
+            "obstacles": [{"type": "RedirectionLeft", "position": {"x": 1, "y": 1}}]
+        }"#;
+        let expected = JsonMaze {
+            width: 3,
+            height: 2,
+            squares: vec![
+                ("S3:2".to_string(), Point::new(0, 0)),
+                ("F2".to_string(), Point::new(2, 0)),
+                ("DL".to_string(), Point::new(1, 1)),
+            ],
+        };
+        assert_eq!(from_json(json), Ok(expected));
+    }
+
+    #[test]
+    fn test_from_json_missing_size_is_error() {
+        assert_eq!(
+            from_json(r#"{"bombs": []}"#),
+            Err(BombermanError::InvalidJson(
+                "maze is missing the field size".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_from_json_too_big_size_is_error() {
+        for (width, height) in [
+            (70000_u32, 70000_u32),
+            (4000000000, 4000000000),
+            (1001, 1000),
+        ] {
+            let json = format!(r#"{{"size": {{"width": {width}, "height": {height}}}}}"#);
+            assert_eq!(
+                from_json(&json),
+                Err(BombermanError::InvalidJson(format!(
+                    "maze size {width}x{height} has more than 1000000 squares"
+                )))
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_json_unknown_obstacle_is_error() {
+        let json = r#"{"size": {"width": 1, "height": 1},
+            "obstacles": [{"type": "Lava", "position": {"x": 0, "y": 0}}]}"#;
+        assert_eq!(
+            from_json(json),
+            Err(BombermanError::InvalidJson(
+                "invalid obstacle type Lava".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_bomb_to_json_includes_fuse() {
+        let bomb = Bomb::new("B3:2", Point::new(1, 0)).unwrap();
+        assert_eq!(
+            bomb_to_json(&bomb).to_string(),
+            r#"{"type":"Normal","range":3,"fuse":2,"position":{"x":1,"y":0}}"#
+        );
+    }
+
+    #[test]
+    fn test_from_json_teleporter_needs_id() {
+        let json = r#"{"size": {"width": 2, "height": 1},
+            "obstacles": [{"type": "Teleporter", "id": 4, "position": {"x": 0, "y": 0}},
+                          {"type": "Teleporter", "position": {"x": 1, "y": 0}}]}"#;
+        assert_eq!(
+            from_json(json),
+            Err(BombermanError::InvalidJson(
+                "obstacle is missing the field id".to_string()
+            ))
+        );
+    }
+}
@@ -1,38 +1,67 @@
 use crate::bomberman_errors::BombermanError;
+use crate::bomberman_events::BombermanEvent;
 use crate::maze_placeable::bomb_state::BombState;
 use crate::maze_placeable::bomb_type::BombType;
 use crate::maze_placeable::obstacle::Obstacle;
+use crate::maze_placeable::obstacle_type::ObstacleType;
 use crate::utils::can_be_hit::CanBeHit;
 use crate::utils::direction::Direction;
+use crate::utils::grid::Grid;
 use crate::utils::maze_display::MazeDisplay;
 use crate::utils::point::Point;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Bomb {
     bomb_type: BombType,
     bomb_state: BombState,
     position: Point,
     explosion_distance: u32,
+    fuse: Option<u32>,
 }
 
+pub const FUSE_SEPARATOR: char = ':';
+
 impl Bomb {
     // Create a new bomb from a square and a position
     // The square should start with B or S and be followed by a number greater than 0
+    // Optionally it can end with :<fuse>, the number of turns greater than 0 the bomb
+    // waits to explode after being hit. Eg: B3:2
     // Return an error if the square is invalid
     pub fn new(square: &str, position: Point) -> Result<Bomb, BombermanError> {
         let Ok(bomb_type) = BombType::new(square) else {
-            return Err(BombermanError::InvalidSquare(format!(
-                "invalid bomb {square} at {position}. It should start with B or S"
-            )))
+            return Err(BombermanError::invalid_square(
+                position,
+                square,
+                &format!("invalid bomb {square} at {position}. It should start with B or S"),
+            ));
+        };
+
+        let (distance, fuse) = match square[1..].split_once(FUSE_SEPARATOR) {
+            Some((distance, fuse)) => (distance, Some(fuse)),
+            None => (&square[1..], None),
         };
 
-        let explosion_distance = match square[1..].parse::<u32>() {
+        let fuse = match fuse.map(str::parse::<u32>) {
+            None => None,
+            Some(Ok(fuse)) if fuse > 0 => Some(fuse),
+            Some(_) => {
+                return Err(BombermanError::invalid_square(
+                    position,
+                    square,
+                    &format!("invalid bomb fuse {square} at {position} it should be positive number greater than 0"),
+                ))
+            }
+        };
+
+        let explosion_distance = match distance.parse::<u32>() {
             Ok(bomb_distance) if bomb_distance > 0 => bomb_distance,
             _ => {
-                return Err(BombermanError::InvalidSquare(format!(
-                    "invalid bomb distance {square} at {position} it should be positive number greater than 0"
-                )))
+                return Err(BombermanError::invalid_square(
+                    position,
+                    square,
+                    &format!("invalid bomb distance {square} at {position} it should be positive number greater than 0"),
+                ))
             }
         };
         Ok(Bomb {
@@ -40,38 +69,159 @@ impl Bomb {
             bomb_state: BombState::Idle,
             position,
             explosion_distance,
+            fuse,
         })
     }
 
+    // Return the type of the bomb
+    pub fn bomb_type(&self) -> BombType {
+        self.bomb_type
+    }
+
+    // Return the number of squares the explosion reaches in each direction
+    pub fn explosion_distance(&self) -> u32 {
+        self.explosion_distance
+    }
+
+    // Return the number of turns the bomb waits to explode after being hit, if it has a fuse
+    pub fn fuse(&self) -> Option<u32> {
+        self.fuse
+    }
+
+    // Return true if the bomb has not been hit yet
+    pub fn is_idle(&self) -> bool {
+        self.bomb_state == BombState::Idle
+    }
+
+    // Return true if the bomb has already exploded
+    pub fn is_exploded(&self) -> bool {
+        self.bomb_state == BombState::Exploded
+    }
+
     // Return true if the bomb is active
     pub fn is_active(&self) -> bool {
         self.bomb_state == BombState::Activated
     }
 
     // Explode the bomb and return the list of points affected by the explosion
-    pub fn explode(&mut self, maze_size: u32, obstacles: &[Obstacle]) -> Vec<Point> {
+    // The grid is used to find the obstacles in the way of the explosion
+    pub fn explode(&mut self, grid: &Grid, obstacles: &[Obstacle]) -> Vec<Point> {
+        self.explode_with_events(grid, obstacles, &mut Vec::new())
+    }
+
+    // Explode the bomb and return the list of points affected by the explosion
+    // Every obstacle that stops, redirects, splits or teleports the explosion is added to the events
+    // The explosion is walked as a queue of branches, one for each direction of the bomb
+    // and two more each time a splitter is reached, each with the distance it has left
+    // Each point is returned once even if several branches reach it
+    // A branch that gets back to a point it already left in the same direction is in a loop,
+    // it is stopped and the loop is added to the events. A branch that gets to a point another
+    // one already left in the same direction with at least as much distance is also stopped,
+    // it would only reach the same points. So the walk ends even with the largest distances
+    pub fn explode_with_events(
+        &mut self,
+        grid: &Grid,
+        obstacles: &[Obstacle],
+        events: &mut Vec<BombermanEvent>,
+    ) -> Vec<Point> {
         self.bomb_state = BombState::Exploded;
         let mut explosion_points = HashSet::from([self.position]);
+        // Largest distance the explosion had left when it left each point in each direction
+        // Only the points where it turns or jumps are kept, a loop always has one of them
+        let mut reached: HashMap<(Point, Direction), u32> = HashMap::new();
+        let mut branches = VecDeque::new();
+        for direction in Direction::iter() {
+            let mut branch = Branch::new(self.position, direction, self.explosion_distance);
+            branch.visit(
+                &mut reached,
+                (self.position, direction),
+                self.explosion_distance,
+            );
+            branches.push_back(branch);
+        }
 
-        for dir in Direction::iter() {
-            let mut move_dir = dir;
-            let mut affected_point = self.position;
-            for _ in 0..self.explosion_distance {
-                affected_point = match affected_point.next_point(move_dir, maze_size) {
-                    Ok(x) => x,
-                    Err(_) => break,
-                };
+        while let Some(mut branch) = branches.pop_front() {
+            let mut move_dir = branch.direction;
+            let mut affected_point = branch.start;
+            for step in 1..=branch.distance {
+                affected_point =
+                    match affected_point.next_point(move_dir, grid.width(), grid.height()) {
+                        Ok(x) => x,
+                        Err(_) => break,
+                    };
+                let distance = branch.distance - step;
 
-                let obstacle = obstacles
-                    .iter()
-                    .find(|obstacle| obstacle.is_in_position(affected_point));
+                let index = grid.obstacle_at(affected_point);
+                let obstacle = index.map(|index| &obstacles[index]);
 
                 match obstacle {
+                    Some(obstacle) if obstacle.obstacle_type == ObstacleType::Splitter => {
+                        explosion_points.insert(affected_point);
+                        let to = move_dir.perpendicular();
+                        events.push(BombermanEvent::ExplosionSplit {
+                            position: affected_point,
+                            from: move_dir,
+                            to,
+                        });
+                        for direction in to {
+                            let mut arm = branch.split(affected_point, direction, distance);
+                            match arm.visit(&mut reached, (affected_point, direction), distance) {
+                                Visit::New => branches.push_back(arm),
+                                Visit::Loop => events.push(BombermanEvent::ExplosionLooped {
+                                    position: affected_point,
+                                    direction,
+                                }),
+                                Visit::Covered => (),
+                            }
+                        }
+                        break;
+                    }
                     Some(obstacle) if obstacle.explosion_can_pass(self.bomb_type) => {
-                        move_dir = obstacle.next_direction(move_dir);
                         explosion_points.insert(affected_point);
+                        let partner = index.and_then(|index| grid.partner(index));
+                        if let Some(partner) = partner.map(|partner| &obstacles[partner]) {
+                            // The explosion goes on from the partner without using distance
+                            events.push(BombermanEvent::ExplosionTeleported {
+                                from: affected_point,
+                                to: partner.position,
+                            });
+                            explosion_points.insert(partner.position);
+                            affected_point = partner.position;
+                        } else {
+                            let next_dir = obstacle.next_direction(move_dir);
+                            if next_dir == move_dir {
+                                continue;
+                            }
+                            events.push(BombermanEvent::ExplosionRedirected {
+                                position: affected_point,
+                                from: move_dir,
+                                to: next_dir,
+                            });
+                            move_dir = next_dir;
+                        }
+                        match branch.visit(&mut reached, (affected_point, move_dir), distance) {
+                            Visit::New => (),
+                            Visit::Loop => {
+                                events.push(BombermanEvent::ExplosionLooped {
+                                    position: affected_point,
+                                    direction: move_dir,
+                                });
+                                break;
+                            }
+                            Visit::Covered => break,
+                        }
+                    }
+                    Some(obstacle) => {
+                        events.push(BombermanEvent::ExplosionStopped {
+                            position: affected_point,
+                            obstacle: obstacle.obstacle_type,
+                        });
+                        // A breakable block stops the explosion but is hit by it
+                        if obstacle.is_breakable() {
+                            explosion_points.insert(affected_point);
+                        }
+                        break;
                     }
-                    Some(_) => break,
                     None => {
                         explosion_points.insert(affected_point);
                     }
@@ -82,12 +232,73 @@ impl Bomb {
     }
 }
 
+// Part of an explosion that goes from the start in a direction, until its distance runs out
+// or something stops it
+// The path has every point the branch, or the branches it was split from, left
+// after turning or jumping, with the direction it left in
+struct Branch {
+    start: Point,
+    direction: Direction,
+    distance: u32,
+    path: HashSet<(Point, Direction)>,
+}
+
+// Result of a branch leaving a point in a direction
+enum Visit {
+    // No branch left the point in the direction with as much distance
+    New,
+    // The branch already left the point in the direction, it is in a loop
+    Loop,
+    // Another branch left the point in the direction with at least as much distance
+    Covered,
+}
+
+impl Branch {
+    fn new(start: Point, direction: Direction, distance: u32) -> Branch {
+        Branch {
+            start,
+            direction,
+            distance,
+            path: HashSet::new(),
+        }
+    }
+
+    // Return a new branch that leaves the point in the direction, with the path of this one
+    fn split(&self, start: Point, direction: Direction, distance: u32) -> Branch {
+        Branch {
+            path: self.path.clone(),
+            ..Branch::new(start, direction, distance)
+        }
+    }
+
+    // Record that the branch leaves the point in the direction with the distance left
+    fn visit(
+        &mut self,
+        reached: &mut HashMap<(Point, Direction), u32>,
+        state: (Point, Direction),
+        distance: u32,
+    ) -> Visit {
+        if !self.path.insert(state) {
+            return Visit::Loop;
+        }
+        match reached.get(&state) {
+            Some(best) if *best >= distance => Visit::Covered,
+            _ => {
+                reached.insert(state, distance);
+                Visit::New
+            }
+        }
+    }
+}
+
 impl CanBeHit for Bomb {
     // Bomb only change state when it is idle, else it will be ignored
-    fn hit(&mut self) {
-        if self.bomb_state == BombState::Idle {
-            self.bomb_state = BombState::Activated
+    fn hit(&mut self) -> Option<BombermanEvent> {
+        if self.bomb_state != BombState::Idle {
+            return None;
         }
+        self.bomb_state = BombState::Activated;
+        Some(BombermanEvent::BombActivated(self.position))
     }
 
     // Return true if the bomb is in the given position
@@ -98,13 +309,18 @@ impl CanBeHit for Bomb {
 
 impl MazeDisplay for Bomb {
     // Display the bomb as B<distance> or S<distance> if it not exploded. Else it will be displayed as _
+    // Bombs with a fuse are displayed with it. Eg: B3:2
     fn display(&self) -> String {
         if self.bomb_state == BombState::Exploded {
             return "_".to_string();
         }
-        match self.bomb_type {
+        let bomb = match self.bomb_type {
             BombType::Normal => format!("B{}", self.explosion_distance),
             BombType::Penetrating => format!("S{}", self.explosion_distance),
+        };
+        match self.fuse {
+            Some(fuse) => format!("{bomb}{FUSE_SEPARATOR}{fuse}"),
+            None => bomb,
         }
     }
 
@@ -117,6 +333,7 @@ impl MazeDisplay for Bomb {
 mod test {
     use super::*;
     use crate::maze_placeable::obstacle_type;
+    use crate::maze_placeable::obstacle_type::ObstacleType;
 
     #[test]
     fn test_new_normal_bomb() {
@@ -128,6 +345,7 @@ mod test {
                 bomb_state: BombState::Idle,
                 position: Point::new(0, 0),
                 explosion_distance: 3,
+                fuse: None,
             })
         );
     }
@@ -142,17 +360,67 @@ mod test {
                 bomb_state: BombState::Idle,
                 position: Point::new(0, 0),
                 explosion_distance: 3,
+                fuse: None,
             })
         );
     }
 
+    #[test]
+    fn test_new_bomb_with_fuse() {
+        let bomb = Bomb::new("B3:2", Point::new(0, 0));
+        assert_eq!(
+            bomb,
+            Ok(Bomb {
+                bomb_type: BombType::Normal,
+                bomb_state: BombState::Idle,
+                position: Point::new(0, 0),
+                explosion_distance: 3,
+                fuse: Some(2),
+            })
+        );
+    }
+
+    #[test]
+    fn test_new_bomb_with_zero_fuse_error() {
+        let bomb = Bomb::new("S3:0", Point::new(0, 0));
+        assert_eq!(
+            bomb,
+            Err(BombermanError::invalid_square(
+                Point::new(0, 0),
+                "S3:0",
+                "invalid bomb fuse S3:0 at (0, 0) it should be positive number greater than 0"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_new_bomb_with_invalid_fuse_error() {
+        let bomb = Bomb::new("B3:", Point::new(0, 0));
+        assert_eq!(
+            bomb,
+            Err(BombermanError::invalid_square(
+                Point::new(0, 0),
+                "B3:",
+                "invalid bomb fuse B3: at (0, 0) it should be positive number greater than 0"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_display_bomb_with_fuse() {
+        let bomb = Bomb::new("S2:4", Point::new(0, 0)).unwrap();
+        assert_eq!(bomb.display(), "S2:4");
+    }
+
     #[test]
     fn test_new_bomb_with_invalid_bomb_type() {
         let bomb = Bomb::new("A3", Point::new(0, 0));
         assert_eq!(
             bomb,
-            Err(BombermanError::InvalidSquare(
-                "invalid bomb A3 at (0, 0). It should start with B or S".to_string()
+            Err(BombermanError::invalid_square(
+                Point::new(0, 0),
+                "A3",
+                "invalid bomb A3 at (0, 0). It should start with B or S"
             ))
         );
     }
@@ -162,9 +430,10 @@ mod test {
         let bomb = Bomb::new("B0", Point::new(0, 0));
         assert_eq!(
             bomb,
-            Err(BombermanError::InvalidSquare(
+            Err(BombermanError::invalid_square(
+                Point::new(0, 0),
+                "B0",
                 "invalid bomb distance B0 at (0, 0) it should be positive number greater than 0"
-                    .to_string()
             ))
         );
     }
@@ -174,9 +443,10 @@ mod test {
         let bomb = Bomb::new("Bx", Point::new(0, 0));
         assert_eq!(
             bomb,
-            Err(BombermanError::InvalidSquare(
+            Err(BombermanError::invalid_square(
+                Point::new(0, 0),
+                "Bx",
                 "invalid bomb distance Bx at (0, 0) it should be positive number greater than 0"
-                    .to_string()
             ))
         );
     }
@@ -188,10 +458,11 @@ mod test {
             bomb_state: BombState::Idle,
             position: Point::new(0, 0),
             explosion_distance: 3,
+            fuse: None,
         };
-        assert_eq!(bomb.is_active(), false);
+        assert!(!bomb.is_active());
         bomb.bomb_state = BombState::Activated;
-        assert_eq!(bomb.is_active(), true);
+        assert!(bomb.is_active());
     }
 
     #[test]
@@ -201,11 +472,26 @@ mod test {
             bomb_state: BombState::Idle,
             position: Point::new(0, 0),
             explosion_distance: 3,
+            fuse: None,
         };
         bomb.hit();
         assert_eq!(bomb.bomb_state, BombState::Activated);
     }
 
+    #[test]
+    fn test_hit_idle_bomb_returns_activated_event() {
+        let mut bomb = Bomb {
+            bomb_type: BombType::Normal,
+            bomb_state: BombState::Idle,
+            position: Point::new(0, 0),
+            explosion_distance: 3,
+            fuse: None,
+        };
+        let event = bomb.hit();
+        assert_eq!(event, Some(BombermanEvent::BombActivated(Point::new(0, 0))));
+        assert_eq!(bomb.bomb_state, BombState::Activated);
+    }
+
     #[test]
     fn test_hit_activated_bomb() {
         let mut bomb = Bomb {
@@ -213,6 +499,7 @@ mod test {
             bomb_state: BombState::Activated,
             position: Point::new(0, 0),
             explosion_distance: 3,
+            fuse: None,
         };
         bomb.hit();
         assert_eq!(bomb.bomb_state, BombState::Activated);
@@ -225,6 +512,7 @@ mod test {
             bomb_state: BombState::Exploded,
             position: Point::new(0, 0),
             explosion_distance: 3,
+            fuse: None,
         };
         bomb.hit();
         assert_eq!(bomb.bomb_state, BombState::Exploded);
@@ -237,9 +525,11 @@ mod test {
             bomb_state: BombState::Idle,
             position: Point::new(1, 1),
             explosion_distance: 3,
+            fuse: None,
         };
         let obstacles = vec![];
-        let mut explosion_points = bomb.explode(3, &obstacles);
+        let grid = Grid::from_obstacles(3, 3, &obstacles);
+        let mut explosion_points = bomb.explode(&grid, &obstacles);
         let mut result = vec![
             Point::new(1, 1),
             Point::new(1, 0),
@@ -260,9 +550,11 @@ mod test {
             bomb_state: BombState::Idle,
             position: Point::new(1, 1),
             explosion_distance: 3,
+            fuse: None,
         };
         let obstacles = vec![Obstacle::new("R", Point::new(1, 0)).unwrap()];
-        let mut explosion_points = bomb.explode(3, &obstacles);
+        let grid = Grid::from_obstacles(3, 3, &obstacles);
+        let mut explosion_points = bomb.explode(&grid, &obstacles);
         let mut result = vec![
             Point::new(1, 1),
             Point::new(0, 1),
@@ -282,9 +574,11 @@ mod test {
             bomb_state: BombState::Idle,
             position: Point::new(1, 1),
             explosion_distance: 3,
+            fuse: None,
         };
         let obstacles = vec![];
-        let mut explosion_points = bomb.explode(3, &obstacles);
+        let grid = Grid::from_obstacles(3, 3, &obstacles);
+        let mut explosion_points = bomb.explode(&grid, &obstacles);
         let mut result = vec![
             Point::new(1, 1),
             Point::new(1, 0),
@@ -305,9 +599,11 @@ mod test {
             bomb_state: BombState::Idle,
             position: Point::new(1, 1),
             explosion_distance: 3,
+            fuse: None,
         };
         let obstacles = vec![Obstacle::new("W", Point::new(1, 0)).unwrap()];
-        let mut explosion_points = bomb.explode(3, &obstacles);
+        let grid = Grid::from_obstacles(3, 3, &obstacles);
+        let mut explosion_points = bomb.explode(&grid, &obstacles);
         let mut result = vec![
             Point::new(1, 1),
             Point::new(0, 1),
@@ -327,9 +623,11 @@ mod test {
             bomb_state: BombState::Idle,
             position: Point::new(1, 1),
             explosion_distance: 3,
+            fuse: None,
         };
         let obstacles = vec![Obstacle::new("W", Point::new(1, 0)).unwrap()];
-        let mut explosion_points = bomb.explode(3, &obstacles);
+        let grid = Grid::from_obstacles(3, 3, &obstacles);
+        let mut explosion_points = bomb.explode(&grid, &obstacles);
         let mut result = vec![
             Point::new(1, 1),
             Point::new(0, 1),
@@ -349,13 +647,12 @@ mod test {
             bomb_state: BombState::Idle,
             position: Point::new(1, 1),
             explosion_distance: 4,
+            fuse: None,
         };
-        let obstacles = vec![Obstacle::new(
-            obstacle_type::REDIRECTION_LEFT,
-            Point::new(1, 0),
-        )
-        .unwrap()];
-        let mut explosion_points = bomb.explode(3, &obstacles);
+        let obstacles =
+            vec![Obstacle::new(obstacle_type::REDIRECTION_LEFT, Point::new(1, 0)).unwrap()];
+        let grid = Grid::from_obstacles(3, 3, &obstacles);
+        let mut explosion_points = bomb.explode(&grid, &obstacles);
         let mut result = vec![
             Point::new(1, 1),
             Point::new(1, 0),
@@ -377,14 +674,13 @@ mod test {
             bomb_state: BombState::Idle,
             position: Point::new(1, 1),
             explosion_distance: 5,
+            fuse: None,
         };
-        let obstacles = vec![Obstacle::new(
-            obstacle_type::REDIRECTION_DOWN,
-            Point::new(1, 0),
-        )
-        .unwrap()];
+        let obstacles =
+            vec![Obstacle::new(obstacle_type::REDIRECTION_DOWN, Point::new(1, 0)).unwrap()];
         // The explotion up goes (1,0) -> Redirection -> (1,1) -> (1,2) -> (1,2)
-        let mut explosion_points = bomb.explode(3, &obstacles);
+        let grid = Grid::from_obstacles(3, 3, &obstacles);
+        let mut explosion_points = bomb.explode(&grid, &obstacles);
         let mut result = vec![
             Point::new(1, 1),
             Point::new(1, 0),
@@ -397,4 +693,425 @@ mod test {
         explosion_points.sort();
         assert_eq!(explosion_points, result);
     }
+
+    #[test]
+    fn test_bomb_explosion_events_report_stop_and_redirection() {
+        let mut bomb = Bomb {
+            bomb_type: BombType::Normal,
+            bomb_state: BombState::Idle,
+            position: Point::new(1, 1),
+            explosion_distance: 2,
+            fuse: None,
+        };
+        let obstacles = vec![
+            Obstacle::new(obstacle_type::REDIRECTION_LEFT, Point::new(1, 0)).unwrap(),
+            Obstacle::new("W", Point::new(2, 1)).unwrap(),
+        ];
+        let mut events = Vec::new();
+        let grid = Grid::from_obstacles(3, 3, &obstacles);
+        bomb.explode_with_events(&grid, &obstacles, &mut events);
+        assert_eq!(
+            events,
+            vec![
+                BombermanEvent::ExplosionRedirected {
+                    position: Point::new(1, 0),
+                    from: Direction::Up,
+                    to: Direction::Left,
+                },
+                BombermanEvent::ExplosionStopped {
+                    position: Point::new(2, 1),
+                    obstacle: ObstacleType::Wall,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bomb_explosion_in_corridor_checks_each_axis() {
+        let mut bomb = Bomb {
+            bomb_type: BombType::Normal,
+            bomb_state: BombState::Idle,
+            position: Point::new(1, 0),
+            explosion_distance: 3,
+            fuse: None,
+        };
+        let obstacles = vec![];
+        let grid = Grid::from_obstacles(5, 1, &obstacles);
+        let mut explosion_points = bomb.explode(&grid, &obstacles);
+        let mut result = vec![
+            Point::new(0, 0),
+            Point::new(1, 0),
+            Point::new(2, 0),
+            Point::new(3, 0),
+            Point::new(4, 0),
+        ];
+
+        result.sort();
+        explosion_points.sort();
+        assert_eq!(explosion_points, result);
+    }
+
+    #[test]
+    fn test_bomb_explosion_bounces_between_mirrors() {
+        let mut bomb = Bomb {
+            bomb_type: BombType::Normal,
+            bomb_state: BombState::Idle,
+            position: Point::new(0, 2),
+            explosion_distance: 7,
+            fuse: None,
+        };
+        // The explosion right goes (1,2) / up -> (1,1) -> (1,0) / right -> (2,0)
+        // -> (3,0) \ down -> (3,1) -> (3,2)
+        let obstacles = vec![
+            Obstacle::new(obstacle_type::MIRROR_SLASH, Point::new(1, 2)).unwrap(),
+            Obstacle::new(obstacle_type::MIRROR_SLASH, Point::new(1, 0)).unwrap(),
+            Obstacle::new(obstacle_type::MIRROR_BACKSLASH, Point::new(3, 0)).unwrap(),
+        ];
+        let mut events = Vec::new();
+        let grid = Grid::from_obstacles(4, 3, &obstacles);
+        let mut explosion_points = bomb.explode_with_events(&grid, &obstacles, &mut events);
+        let mut result = vec![
+            Point::new(0, 2),
+            Point::new(0, 1),
+            Point::new(0, 0),
+            Point::new(1, 2),
+            Point::new(1, 1),
+            Point::new(1, 0),
+            Point::new(2, 0),
+            Point::new(3, 0),
+            Point::new(3, 1),
+            Point::new(3, 2),
+        ];
+
+        result.sort();
+        explosion_points.sort();
+        assert_eq!(explosion_points, result);
+        let turns: Vec<(Direction, Direction)> = events
+            .iter()
+            .filter_map(|event| match event {
+                BombermanEvent::ExplosionRedirected { from, to, .. } => Some((*from, *to)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            turns,
+            vec![
+                (Direction::Right, Direction::Up),
+                (Direction::Up, Direction::Right),
+                (Direction::Right, Direction::Down),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bomb_explosion_reflected_back_by_mirrors() {
+        let mut bomb = Bomb {
+            bomb_type: BombType::Normal,
+            bomb_state: BombState::Idle,
+            position: Point::new(0, 0),
+            explosion_distance: 6,
+            fuse: None,
+        };
+        // The explosion right goes (1,0) \ down -> (1,1) / left -> (0,1)
+        // and the explosion down reaches (0,1) too, it is reported once
+        let obstacles = vec![
+            Obstacle::new(obstacle_type::MIRROR_BACKSLASH, Point::new(1, 0)).unwrap(),
+            Obstacle::new(obstacle_type::MIRROR_SLASH, Point::new(1, 1)).unwrap(),
+        ];
+        let grid = Grid::from_obstacles(2, 2, &obstacles);
+        let mut explosion_points = bomb.explode(&grid, &obstacles);
+        let mut result = vec![
+            Point::new(0, 0),
+            Point::new(1, 0),
+            Point::new(1, 1),
+            Point::new(0, 1),
+        ];
+
+        result.sort();
+        explosion_points.sort();
+        assert_eq!(explosion_points, result);
+    }
+
+    #[test]
+    fn test_bomb_explosion_is_split_with_remaining_distance() {
+        let mut bomb = Bomb {
+            bomb_type: BombType::Normal,
+            bomb_state: BombState::Idle,
+            position: Point::new(0, 2),
+            explosion_distance: 4,
+            fuse: None,
+        };
+        // The explosion right reaches the splitter at (2,2) with 2 squares left
+        let obstacles = vec![Obstacle::new(obstacle_type::SPLITTER, Point::new(2, 2)).unwrap()];
+        let mut events = Vec::new();
+        let grid = Grid::from_obstacles(5, 5, &obstacles);
+        let mut explosion_points = bomb.explode_with_events(&grid, &obstacles, &mut events);
+        let mut result = vec![
+            Point::new(0, 2),
+            Point::new(0, 1),
+            Point::new(0, 0),
+            Point::new(0, 3),
+            Point::new(0, 4),
+            Point::new(1, 2),
+            Point::new(2, 2),
+            Point::new(2, 1),
+            Point::new(2, 0),
+            Point::new(2, 3),
+            Point::new(2, 4),
+        ];
+
+        result.sort();
+        explosion_points.sort();
+        assert_eq!(explosion_points, result);
+        assert_eq!(
+            events,
+            vec![BombermanEvent::ExplosionSplit {
+                position: Point::new(2, 2),
+                from: Direction::Right,
+                to: [Direction::Up, Direction::Down],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_bomb_explosion_split_branches_report_points_once() {
+        let mut bomb = Bomb {
+            bomb_type: BombType::Normal,
+            bomb_state: BombState::Idle,
+            position: Point::new(0, 1),
+            explosion_distance: 6,
+            fuse: None,
+        };
+        // Both arms of the splitter are sent back and forth through (2,1)
+        let obstacles = vec![
+            Obstacle::new(obstacle_type::SPLITTER, Point::new(1, 1)).unwrap(),
+            Obstacle::new(obstacle_type::MIRROR_SLASH, Point::new(1, 0)).unwrap(),
+            Obstacle::new(obstacle_type::MIRROR_BACKSLASH, Point::new(1, 2)).unwrap(),
+            Obstacle::new(obstacle_type::REDIRECTION_DOWN, Point::new(2, 0)).unwrap(),
+            Obstacle::new(obstacle_type::REDIRECTION_UP, Point::new(2, 2)).unwrap(),
+        ];
+        let grid = Grid::from_obstacles(3, 3, &obstacles);
+        let explosion_points = bomb.explode(&grid, &obstacles);
+        let unique: HashSet<&Point> = explosion_points.iter().collect();
+
+        assert_eq!(unique.len(), explosion_points.len());
+        assert_eq!(explosion_points.len(), 9);
+    }
+
+    #[test]
+    fn test_bomb_explosion_splitters_in_a_loop_do_not_multiply_branches() {
+        let mut bomb = Bomb {
+            bomb_type: BombType::Penetrating,
+            bomb_state: BombState::Idle,
+            position: Point::new(1, 1),
+            explosion_distance: 1000,
+            fuse: None,
+        };
+        // The mirrors in the corners send every arm of a splitter into the other splitter,
+        // so without a limit the branches double each time they go around
+        let obstacles = vec![
+            Obstacle::new(obstacle_type::MIRROR_SLASH, Point::new(0, 0)).unwrap(),
+            Obstacle::new(obstacle_type::SPLITTER, Point::new(1, 0)).unwrap(),
+            Obstacle::new(obstacle_type::MIRROR_BACKSLASH, Point::new(2, 0)).unwrap(),
+            Obstacle::new(obstacle_type::MIRROR_BACKSLASH, Point::new(0, 2)).unwrap(),
+            Obstacle::new(obstacle_type::SPLITTER, Point::new(1, 2)).unwrap(),
+            Obstacle::new(obstacle_type::MIRROR_SLASH, Point::new(2, 2)).unwrap(),
+        ];
+        let mut events = Vec::new();
+        let grid = Grid::from_obstacles(3, 3, &obstacles);
+        let explosion_points = bomb.explode_with_events(&grid, &obstacles, &mut events);
+
+        assert_eq!(explosion_points.len(), 9);
+        assert!(events.len() < 100);
+    }
+
+    #[test]
+    fn test_bomb_explosion_goes_out_of_partner_teleporter() {
+        let mut bomb = Bomb {
+            bomb_type: BombType::Normal,
+            bomb_state: BombState::Idle,
+            position: Point::new(0, 0),
+            explosion_distance: 3,
+            fuse: None,
+        };
+        // The explosion right enters (1,0) and goes on down from (2,1) with 2 squares left
+        let obstacles = vec![
+            Obstacle::new("T1", Point::new(0, 1)).unwrap(),
+            Obstacle::new("T1", Point::new(2, 1)).unwrap(),
+        ];
+        let mut events = Vec::new();
+        let grid = Grid::from_obstacles(3, 4, &obstacles);
+        let mut explosion_points = bomb.explode_with_events(&grid, &obstacles, &mut events);
+        let mut result = vec![
+            Point::new(0, 0),
+            Point::new(1, 0),
+            Point::new(2, 0),
+            Point::new(0, 1),
+            Point::new(2, 1),
+            Point::new(2, 2),
+            Point::new(2, 3),
+        ];
+
+        result.sort();
+        explosion_points.sort();
+        assert_eq!(explosion_points, result);
+        assert_eq!(
+            events,
+            vec![BombermanEvent::ExplosionTeleported {
+                from: Point::new(0, 1),
+                to: Point::new(2, 1),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_bomb_explosion_in_teleporter_loop_stops_with_its_distance() {
+        let mut bomb = Bomb {
+            bomb_type: BombType::Normal,
+            bomb_state: BombState::Idle,
+            position: Point::new(2, 0),
+            explosion_distance: 10,
+            fuse: None,
+        };
+        // Going right, the explosion leaves (3,0) from (1,0) and gets back to (3,0),
+        // going left it leaves (1,0) from (3,0) and gets back to (1,0)
+        let obstacles = vec![
+            Obstacle::new("T1", Point::new(1, 0)).unwrap(),
+            Obstacle::new("T1", Point::new(3, 0)).unwrap(),
+        ];
+        let grid = Grid::from_obstacles(4, 1, &obstacles);
+        let mut explosion_points = bomb.explode(&grid, &obstacles);
+        let mut result = vec![Point::new(1, 0), Point::new(2, 0), Point::new(3, 0)];
+
+        result.sort();
+        explosion_points.sort();
+        assert_eq!(explosion_points, result);
+    }
+
+    #[test]
+    fn test_bomb_explosion_redirection_loop_is_stopped() {
+        let mut bomb = Bomb {
+            bomb_type: BombType::Penetrating,
+            bomb_state: BombState::Idle,
+            position: Point::new(0, 1),
+            explosion_distance: u32::MAX,
+            fuse: None,
+        };
+        // The explosion up goes back and forth between (0,0) and (1,0)
+        let obstacles = vec![
+            Obstacle::new(obstacle_type::REDIRECTION_RIGHT, Point::new(0, 0)).unwrap(),
+            Obstacle::new(obstacle_type::REDIRECTION_LEFT, Point::new(1, 0)).unwrap(),
+        ];
+        let mut events = Vec::new();
+        let grid = Grid::from_obstacles(2, 2, &obstacles);
+        let explosion_points = bomb.explode_with_events(&grid, &obstacles, &mut events);
+
+        assert_eq!(explosion_points.len(), 4);
+        assert_eq!(
+            events,
+            vec![
+                BombermanEvent::ExplosionRedirected {
+                    position: Point::new(0, 0),
+                    from: Direction::Up,
+                    to: Direction::Right,
+                },
+                BombermanEvent::ExplosionRedirected {
+                    position: Point::new(1, 0),
+                    from: Direction::Right,
+                    to: Direction::Left,
+                },
+                BombermanEvent::ExplosionRedirected {
+                    position: Point::new(0, 0),
+                    from: Direction::Left,
+                    to: Direction::Right,
+                },
+                BombermanEvent::ExplosionLooped {
+                    position: Point::new(0, 0),
+                    direction: Direction::Right,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bomb_explosion_with_largest_distance_stops_at_the_edges() {
+        let mut bomb = Bomb {
+            bomb_type: BombType::Penetrating,
+            bomb_state: BombState::Idle,
+            position: Point::new(1, 1),
+            explosion_distance: u32::MAX,
+            fuse: None,
+        };
+        let obstacles = vec![Obstacle::new("R", Point::new(1, 0)).unwrap()];
+        let grid = Grid::from_obstacles(3, 3, &obstacles);
+        let explosion_points = bomb.explode(&grid, &obstacles);
+
+        assert_eq!(explosion_points.len(), 5);
+    }
+
+    #[test]
+    fn test_bomb_explosion_teleporter_loop_is_stopped() {
+        let mut bomb = Bomb {
+            bomb_type: BombType::Normal,
+            bomb_state: BombState::Idle,
+            position: Point::new(2, 0),
+            explosion_distance: u32::MAX,
+            fuse: None,
+        };
+        let obstacles = vec![
+            Obstacle::new("T1", Point::new(1, 0)).unwrap(),
+            Obstacle::new("T1", Point::new(3, 0)).unwrap(),
+        ];
+        let mut events = Vec::new();
+        let grid = Grid::from_obstacles(4, 1, &obstacles);
+        bomb.explode_with_events(&grid, &obstacles, &mut events);
+
+        let loops: Vec<&BombermanEvent> = events
+            .iter()
+            .filter(|event| matches!(event, BombermanEvent::ExplosionLooped { .. }))
+            .collect();
+        assert_eq!(
+            loops,
+            vec![
+                &BombermanEvent::ExplosionLooped {
+                    position: Point::new(3, 0),
+                    direction: Direction::Left,
+                },
+                &BombermanEvent::ExplosionLooped {
+                    position: Point::new(1, 0),
+                    direction: Direction::Right,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bomb_explosion_loop_through_splitter_is_stopped() {
+        let mut bomb = Bomb {
+            bomb_type: BombType::Normal,
+            bomb_state: BombState::Idle,
+            position: Point::new(1, 0),
+            explosion_distance: u32::MAX,
+            fuse: None,
+        };
+        // The arm left of the splitter goes around the mirrors and gets back to it from below
+        let obstacles = vec![
+            Obstacle::new(obstacle_type::SPLITTER, Point::new(1, 1)).unwrap(),
+            Obstacle::new(obstacle_type::MIRROR_SLASH, Point::new(0, 1)).unwrap(),
+            Obstacle::new(obstacle_type::MIRROR_BACKSLASH, Point::new(0, 2)).unwrap(),
+            Obstacle::new(obstacle_type::MIRROR_SLASH, Point::new(1, 2)).unwrap(),
+        ];
+        let mut events = Vec::new();
+        let grid = Grid::from_obstacles(2, 3, &obstacles);
+        let explosion_points = bomb.explode_with_events(&grid, &obstacles, &mut events);
+
+        assert_eq!(explosion_points.len(), 6);
+        assert_eq!(
+            events.last(),
+            Some(&BombermanEvent::ExplosionLooped {
+                position: Point::new(1, 1),
+                direction: Direction::Left,
+            })
+        );
+    }
 }
@@ -1,4 +1,5 @@
 use crate::bomberman_errors::BombermanError;
+use crate::bomberman_events::BombermanEvent;
 use crate::maze_placeable::enemy_state::EnemyState;
 use crate::utils::can_be_hit::CanBeHit;
 use crate::utils::maze_display::MazeDisplay;
@@ -6,7 +7,7 @@ use crate::utils::point::Point;
 
 pub const ENEMY: &str = "F";
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Enemy {
     health: u32,
     position: Point,
@@ -19,17 +20,21 @@ impl Enemy {
     // Return an error if the square is invalid
     pub fn new(square: &str, position: Point) -> Result<Enemy, BombermanError> {
         if !square.starts_with(ENEMY) {
-            return Err(BombermanError::InvalidSquare(format!(
-                "invalid enemy {square} at {position}"
-            )));
+            return Err(BombermanError::invalid_square(
+                position,
+                square,
+                &format!("invalid enemy {square} at {position}"),
+            ));
         }
 
         let health = match square[1..].parse::<u32>() {
             Ok(health) if health > 0 && health < 4 => health,
             _ => {
-                return Err(BombermanError::InvalidSquare(format!(
-                    "invalid enemy health {square} at {position}. It should be a positive number between 1 and 3 included"
-                )))
+                return Err(BombermanError::invalid_square(
+                    position,
+                    square,
+                    &format!("invalid enemy health {square} at {position}. It should be a positive number between 1 and 3 included"),
+                ))
             }
         };
 
@@ -40,6 +45,16 @@ impl Enemy {
         })
     }
 
+    // Return the remaining health of the enemy
+    pub fn health(&self) -> u32 {
+        self.health
+    }
+
+    // Return true if the enemy is not dead
+    pub fn is_alive(&self) -> bool {
+        self.state != EnemyState::Dead
+    }
+
     // If the enemy is hit, reset the state to NotHit for the next turn
     pub(crate) fn reset_state(&mut self) {
         if self.state == EnemyState::Hit {
@@ -51,18 +66,23 @@ impl Enemy {
 impl CanBeHit for Enemy {
     // If the enemy is Idle, hit it and set the state to Hit
     // If the enemy is Hit or Dead, do nothing
-    fn hit(&mut self) {
+    fn hit(&mut self) -> Option<BombermanEvent> {
         match self.state {
             EnemyState::Idle => {
                 self.health -= 1;
                 if self.health == 0 {
                     self.state = EnemyState::Dead;
+                    Some(BombermanEvent::EnemyKilled(self.position))
                 } else {
                     self.state = EnemyState::Hit;
+                    Some(BombermanEvent::EnemyHit {
+                        position: self.position,
+                        health: self.health,
+                    })
                 }
             }
-            EnemyState::Hit => (),
-            EnemyState::Dead => (),
+            EnemyState::Hit => None,
+            EnemyState::Dead => None,
         }
     }
 
@@ -109,8 +129,10 @@ mod test {
         let enemy = Enemy::new("A3", Point::new(0, 0));
         assert_eq!(
             enemy,
-            Err(BombermanError::InvalidSquare(
-                "invalid enemy A3 at (0, 0)".to_string()
+            Err(BombermanError::invalid_square(
+                Point::new(0, 0),
+                "A3",
+                "invalid enemy A3 at (0, 0)"
             ))
         );
     }
@@ -120,8 +142,10 @@ mod test {
         let enemy = Enemy::new("F", Point::new(0, 0));
         assert_eq!(
             enemy,
-            Err(BombermanError::InvalidSquare(
-                "invalid enemy health F at (0, 0). It should be a positive number between 1 and 3 included".to_string()
+            Err(BombermanError::invalid_square(
+                Point::new(0, 0),
+                "F",
+                "invalid enemy health F at (0, 0). It should be a positive number between 1 and 3 included"
             ))
         );
     }
@@ -131,8 +155,10 @@ mod test {
         let enemy = Enemy::new("F3A", Point::new(0, 0));
         assert_eq!(
             enemy,
-            Err(BombermanError::InvalidSquare(
-                "invalid enemy health F3A at (0, 0). It should be a positive number between 1 and 3 included".to_string()
+            Err(BombermanError::invalid_square(
+                Point::new(0, 0),
+                "F3A",
+                "invalid enemy health F3A at (0, 0). It should be a positive number between 1 and 3 included"
             ))
         );
     }
@@ -142,8 +168,10 @@ mod test {
         let enemy = Enemy::new("F3.5", Point::new(0, 0));
         assert_eq!(
             enemy,
-            Err(BombermanError::InvalidSquare(
-                "invalid enemy health F3.5 at (0, 0). It should be a positive number between 1 and 3 included".to_string()
+            Err(BombermanError::invalid_square(
+                Point::new(0, 0),
+                "F3.5",
+                "invalid enemy health F3.5 at (0, 0). It should be a positive number between 1 and 3 included"
             ))
         );
     }
@@ -153,8 +181,10 @@ mod test {
         let enemy = Enemy::new("F-3", Point::new(0, 0));
         assert_eq!(
             enemy,
-            Err(BombermanError::InvalidSquare(
-                "invalid enemy health F-3 at (0, 0). It should be a positive number between 1 and 3 included".to_string()
+            Err(BombermanError::invalid_square(
+                Point::new(0, 0),
+                "F-3",
+                "invalid enemy health F-3 at (0, 0). It should be a positive number between 1 and 3 included"
             ))
         );
     }
@@ -164,8 +194,10 @@ mod test {
         let enemy = Enemy::new("F0", Point::new(0, 0));
         assert_eq!(
             enemy,
-            Err(BombermanError::InvalidSquare(
-                "invalid enemy health F0 at (0, 0). It should be a positive number between 1 and 3 included".to_string()
+            Err(BombermanError::invalid_square(
+                Point::new(0, 0),
+                "F0",
+                "invalid enemy health F0 at (0, 0). It should be a positive number between 1 and 3 included"
             ))
         );
     }
@@ -175,8 +207,10 @@ mod test {
         let enemy = Enemy::new("F4", Point::new(0, 0));
         assert_eq!(
             enemy,
-            Err(BombermanError::InvalidSquare(
-                "invalid enemy health F4 at (0, 0). It should be a positive number between 1 and 3 included".to_string()
+            Err(BombermanError::invalid_square(
+                Point::new(0, 0),
+                "F4",
+                "invalid enemy health F4 at (0, 0). It should be a positive number between 1 and 3 included"
             ))
         );
     }
@@ -307,6 +341,25 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_hit_to_dead_returns_killed_event() {
+        let mut enemy = Enemy {
+            health: 1,
+            position: Point::new(0, 0),
+            state: EnemyState::Idle,
+        };
+        let event = enemy.hit();
+        assert_eq!(
+            enemy,
+            Enemy {
+                health: 0,
+                position: Point::new(0, 0),
+                state: EnemyState::Dead,
+            }
+        );
+        assert_eq!(event, Some(BombermanEvent::EnemyKilled(Point::new(0, 0))));
+    }
+
     #[test]
     fn test_in_position_equal_position_at_position() {
         let enemy = Enemy {
@@ -316,7 +369,7 @@ mod test {
         };
         let position = Point::new(0, 0);
         let result = enemy.in_position(position);
-        assert_eq!(result, true);
+        assert!(result);
     }
 
     #[test]
@@ -328,7 +381,7 @@ mod test {
         };
         let position = Point::new(0, 1);
         let result = enemy.in_position(position);
-        assert_eq!(result, false);
+        assert!(!result);
     }
 
     #[test]
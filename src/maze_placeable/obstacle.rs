@@ -1,11 +1,13 @@
 use crate::bomberman_errors::BombermanError;
+use crate::bomberman_events::BombermanEvent;
 use crate::maze_placeable::bomb_type::BombType;
 use crate::maze_placeable::obstacle_type::ObstacleType;
+use crate::utils::can_be_hit::CanBeHit;
 use crate::utils::direction::Direction;
 use crate::utils::maze_display::MazeDisplay;
 use crate::utils::point::Point;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Obstacle {
     pub(crate) obstacle_type: ObstacleType,
     pub(crate) position: Point,
@@ -20,6 +22,11 @@ impl Obstacle {
     // DD: redirection down
     // DL: redirection left
     // DR: redirection right
+    // X or X<hits>: breakable block that breaks after that many hits, 1 by default
+    // /: mirror, reflects right to up, up to right, left to down and down to left
+    // \: mirror, reflects right to down, down to right, left to up and up to left
+    // +: splitter, splits the explosion in the two directions perpendicular to the one it comes from
+    // T<id>: teleporter, sends the explosion out of the other teleporter with the same id
     // Return an error if the square is invalid
     pub fn new(square: &str, position: Point) -> Result<Obstacle, BombermanError> {
         match ObstacleType::new(square) {
@@ -27,9 +34,11 @@ impl Obstacle {
                 obstacle_type,
                 position,
             }),
-            Err(_) => Err(BombermanError::InvalidSquare(format!(
-                "invalid obstacle {square} at {position}"
-            ))),
+            Err(_) => Err(BombermanError::invalid_square(
+                position,
+                square,
+                &format!("invalid obstacle {square} at {position}"),
+            )),
         }
     }
 
@@ -39,26 +48,82 @@ impl Obstacle {
     }
 
     // Return true if the explosion can pass through the obstacle
+    // Normal explosions are stopped by walls, rocks and breakable blocks that are not broken.
+    // Penetrating explosions are only stopped by walls, they go through breakable blocks
+    // like rocks but still hit them
     pub fn explosion_can_pass(&self, bomb_type: BombType) -> bool {
-        match bomb_type {
-            BombType::Normal => {
-                self.obstacle_type != ObstacleType::Wall && self.obstacle_type != ObstacleType::Rock
-            }
-            BombType::Penetrating => self.obstacle_type != ObstacleType::Wall,
+        match (bomb_type, self.obstacle_type) {
+            (_, ObstacleType::Wall) => false,
+            (BombType::Normal, ObstacleType::Rock) => false,
+            (BombType::Normal, ObstacleType::Breakable(hits)) => hits == 0,
+            _ => true,
         }
     }
 
-    // Return the next direction to take if the obstacle is a redirection
+    // Return true if the obstacle can still be broken by an explosion
+    pub fn is_breakable(&self) -> bool {
+        matches!(self.obstacle_type, ObstacleType::Breakable(hits) if hits > 0)
+    }
+
+    // Return true if the obstacle was a breakable block that is already broken
+    pub fn is_broken(&self) -> bool {
+        self.obstacle_type == ObstacleType::Breakable(0)
+    }
+
+    // Return the next direction to take if the obstacle is a redirection or a mirror
     // Otherwise return the same direction
     pub fn next_direction(&self, direction: Direction) -> Direction {
-        match self.obstacle_type {
-            ObstacleType::RedirectionUp => Direction::Up,
-            ObstacleType::RedirectionDown => Direction::Down,
-            ObstacleType::RedirectionLeft => Direction::Left,
-            ObstacleType::RedirectionRight => Direction::Right,
+        match (self.obstacle_type, direction) {
+            (ObstacleType::RedirectionUp, _) => Direction::Up,
+            (ObstacleType::RedirectionDown, _) => Direction::Down,
+            (ObstacleType::RedirectionLeft, _) => Direction::Left,
+            (ObstacleType::RedirectionRight, _) => Direction::Right,
+            (ObstacleType::MirrorSlash, Direction::Right) => Direction::Up,
+            (ObstacleType::MirrorSlash, Direction::Up) => Direction::Right,
+            (ObstacleType::MirrorSlash, Direction::Left) => Direction::Down,
+            (ObstacleType::MirrorSlash, Direction::Down) => Direction::Left,
+            (ObstacleType::MirrorBackslash, Direction::Right) => Direction::Down,
+            (ObstacleType::MirrorBackslash, Direction::Down) => Direction::Right,
+            (ObstacleType::MirrorBackslash, Direction::Left) => Direction::Up,
+            (ObstacleType::MirrorBackslash, Direction::Up) => Direction::Left,
             _ => direction,
         }
     }
+
+    // Return the directions the explosion continues in after the obstacle
+    // A splitter returns the two perpendicular directions, other obstacles only the next direction
+    pub fn next_directions(&self, direction: Direction) -> Vec<Direction> {
+        match self.obstacle_type {
+            ObstacleType::Splitter => direction.perpendicular().to_vec(),
+            _ => vec![self.next_direction(direction)],
+        }
+    }
+}
+
+impl CanBeHit for Obstacle {
+    // Breakable blocks lose a hit and break when they have none left
+    // Other obstacles are not changed by explosions
+    fn hit(&mut self) -> Option<BombermanEvent> {
+        let ObstacleType::Breakable(hits) = self.obstacle_type else {
+            return None;
+        };
+        if hits == 0 {
+            return None;
+        }
+        self.obstacle_type = ObstacleType::Breakable(hits - 1);
+        if hits == 1 {
+            Some(BombermanEvent::ObstacleBroken(self.position))
+        } else {
+            Some(BombermanEvent::ObstacleHit {
+                position: self.position,
+                hits: hits - 1,
+            })
+        }
+    }
+
+    fn in_position(&self, position: Point) -> bool {
+        self.position == position
+    }
 }
 
 impl MazeDisplay for Obstacle {
@@ -74,7 +139,6 @@ impl MazeDisplay for Obstacle {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::bomberman_errors::BombermanError::InvalidSquare;
 
     #[test]
     fn test_new_wall() {
@@ -167,7 +231,11 @@ mod test {
         let result = Obstacle::new(square, position);
         assert_eq!(
             result,
-            Err(InvalidSquare("invalid obstacle A at (0, 0)".to_string()))
+            Err(BombermanError::invalid_square(
+                Point::new(0, 0),
+                "A",
+                "invalid obstacle A at (0, 0)"
+            ))
         );
     }
 
@@ -179,7 +247,7 @@ mod test {
         };
         let position = Point::new(0, 0);
         let result = obstacle.is_in_position(position);
-        assert_eq!(result, true);
+        assert!(result);
     }
 
     #[test]
@@ -190,7 +258,7 @@ mod test {
         };
         let position = Point::new(0, 1);
         let result = obstacle.is_in_position(position);
-        assert_eq!(result, false);
+        assert!(!result);
     }
 
     #[test]
@@ -237,6 +305,40 @@ mod test {
         assert_eq!(result, Direction::Right);
     }
 
+    #[test]
+    fn test_normal_explosion_is_stopped_by_breakable() {
+        let obstacle = Obstacle::new("X2", Point::new(0, 0)).unwrap();
+        assert!(!obstacle.explosion_can_pass(BombType::Normal));
+        assert!(obstacle.explosion_can_pass(BombType::Penetrating));
+    }
+
+    #[test]
+    fn test_breakable_breaks_after_its_hits() {
+        let mut obstacle = Obstacle::new("X2", Point::new(1, 0)).unwrap();
+        assert_eq!(
+            obstacle.hit(),
+            Some(BombermanEvent::ObstacleHit {
+                position: Point::new(1, 0),
+                hits: 1
+            })
+        );
+        assert_eq!(
+            obstacle.hit(),
+            Some(BombermanEvent::ObstacleBroken(Point::new(1, 0)))
+        );
+        assert!(obstacle.is_broken());
+        assert_eq!(obstacle.display(), "_");
+        assert!(obstacle.explosion_can_pass(BombType::Normal));
+        assert_eq!(obstacle.hit(), None);
+    }
+
+    #[test]
+    fn test_hit_does_not_change_rock() {
+        let mut obstacle = Obstacle::new("R", Point::new(0, 0)).unwrap();
+        assert_eq!(obstacle.hit(), None);
+        assert_eq!(obstacle.display(), "R");
+    }
+
     #[test]
     fn test_next_direction_for_not_redirection() {
         let obstacle = Obstacle {
@@ -247,4 +349,50 @@ mod test {
         let result = obstacle.next_direction(direction);
         assert_eq!(result, Direction::Left);
     }
+
+    #[test]
+    fn test_next_direction_for_slash_mirror() {
+        let obstacle = Obstacle::new("/", Point::new(0, 0)).unwrap();
+        assert_eq!(obstacle.next_direction(Direction::Right), Direction::Up);
+        assert_eq!(obstacle.next_direction(Direction::Up), Direction::Right);
+        assert_eq!(obstacle.next_direction(Direction::Left), Direction::Down);
+        assert_eq!(obstacle.next_direction(Direction::Down), Direction::Left);
+    }
+
+    #[test]
+    fn test_next_direction_for_backslash_mirror() {
+        let obstacle = Obstacle::new("\\", Point::new(0, 0)).unwrap();
+        assert_eq!(obstacle.next_direction(Direction::Right), Direction::Down);
+        assert_eq!(obstacle.next_direction(Direction::Down), Direction::Right);
+        assert_eq!(obstacle.next_direction(Direction::Left), Direction::Up);
+        assert_eq!(obstacle.next_direction(Direction::Up), Direction::Left);
+    }
+
+    #[test]
+    fn test_explosion_can_pass_mirror() {
+        let obstacle = Obstacle::new("/", Point::new(0, 0)).unwrap();
+        assert!(obstacle.explosion_can_pass(BombType::Normal));
+    }
+
+    #[test]
+    fn test_next_directions_for_splitter() {
+        let obstacle = Obstacle::new("+", Point::new(0, 0)).unwrap();
+        assert_eq!(
+            obstacle.next_directions(Direction::Right),
+            vec![Direction::Up, Direction::Down]
+        );
+        assert_eq!(
+            obstacle.next_directions(Direction::Down),
+            vec![Direction::Left, Direction::Right]
+        );
+    }
+
+    #[test]
+    fn test_next_directions_for_redirection() {
+        let obstacle = Obstacle::new("DL", Point::new(0, 0)).unwrap();
+        assert_eq!(
+            obstacle.next_directions(Direction::Up),
+            vec![Direction::Left]
+        );
+    }
 }
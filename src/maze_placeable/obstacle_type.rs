@@ -7,8 +7,13 @@ pub const REDIRECTION_UP: &str = "DU";
 pub const REDIRECTION_DOWN: &str = "DD";
 pub const REDIRECTION_LEFT: &str = "DL";
 pub const REDIRECTION_RIGHT: &str = "DR";
+pub const BREAKABLE: &str = "X";
+pub const MIRROR_SLASH: &str = "/";
+pub const MIRROR_BACKSLASH: &str = "\\";
+pub const SPLITTER: &str = "+";
+pub const TELEPORTER: &str = "T";
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum ObstacleType {
     Wall,
     Rock,
@@ -16,6 +21,15 @@ pub enum ObstacleType {
     RedirectionDown,
     RedirectionLeft,
     RedirectionRight,
+    // Block that breaks after the given number of hits, 0 once it is broken
+    Breakable(u32),
+    // Mirrors that reflect the explosion depending on the direction it comes from
+    MirrorSlash,
+    MirrorBackslash,
+    // Splits the explosion in the two directions perpendicular to the one it comes from
+    Splitter,
+    // Portal that sends the explosion out of the other teleporter with the same id
+    Teleporter(u32),
 }
 
 impl ObstacleType {
@@ -27,13 +41,70 @@ impl ObstacleType {
             REDIRECTION_DOWN => Ok(ObstacleType::RedirectionDown),
             REDIRECTION_LEFT => Ok(ObstacleType::RedirectionLeft),
             REDIRECTION_RIGHT => Ok(ObstacleType::RedirectionRight),
+            BREAKABLE => Ok(ObstacleType::Breakable(1)),
+            MIRROR_SLASH => Ok(ObstacleType::MirrorSlash),
+            MIRROR_BACKSLASH => Ok(ObstacleType::MirrorBackslash),
+            SPLITTER => Ok(ObstacleType::Splitter),
+            _ if square.starts_with(BREAKABLE) => match square[BREAKABLE.len()..].parse::<u32>() {
+                Ok(hits) if hits > 0 => Ok(ObstacleType::Breakable(hits)),
+                _ => Err("invalid breakable obstacle".to_string()),
+            },
+            _ if square.starts_with(TELEPORTER) => {
+                match square[TELEPORTER.len()..].parse::<u32>() {
+                    Ok(id) => Ok(ObstacleType::Teleporter(id)),
+                    _ => Err("invalid teleporter".to_string()),
+                }
+            }
             _ => Err("invalid obstacle".to_string()),
         }
     }
 
+    // Create the obstacle type from its name. Eg: Wall, RedirectionUp
+    // Breakable obstacles are created with a single hit and teleporters with id 0
+    pub fn from_name(name: &str) -> Result<ObstacleType, String> {
+        match name {
+            "Wall" => Ok(ObstacleType::Wall),
+            "Rock" => Ok(ObstacleType::Rock),
+            "RedirectionUp" => Ok(ObstacleType::RedirectionUp),
+            "RedirectionDown" => Ok(ObstacleType::RedirectionDown),
+            "RedirectionLeft" => Ok(ObstacleType::RedirectionLeft),
+            "RedirectionRight" => Ok(ObstacleType::RedirectionRight),
+            "Breakable" => Ok(ObstacleType::Breakable(1)),
+            "MirrorSlash" => Ok(ObstacleType::MirrorSlash),
+            "MirrorBackslash" => Ok(ObstacleType::MirrorBackslash),
+            "Splitter" => Ok(ObstacleType::Splitter),
+            "Teleporter" => Ok(ObstacleType::Teleporter(0)),
+            _ => Err(format!("invalid obstacle type {name}")),
+        }
+    }
+
+    // Return the name of the obstacle type, the inverse of from_name
+    pub fn name(&self) -> &'static str {
+        match self {
+            ObstacleType::Wall => "Wall",
+            ObstacleType::Rock => "Rock",
+            ObstacleType::RedirectionUp => "RedirectionUp",
+            ObstacleType::RedirectionDown => "RedirectionDown",
+            ObstacleType::RedirectionLeft => "RedirectionLeft",
+            ObstacleType::RedirectionRight => "RedirectionRight",
+            ObstacleType::Breakable(_) => "Breakable",
+            ObstacleType::MirrorSlash => "MirrorSlash",
+            ObstacleType::MirrorBackslash => "MirrorBackslash",
+            ObstacleType::Splitter => "Splitter",
+            ObstacleType::Teleporter(_) => "Teleporter",
+        }
+    }
+
     // Return true if the square is an obstacle
     pub fn is_obstacle(square: &str) -> bool {
-        square == WALL || square == ROCK || square.starts_with(REDIRECTION)
+        square == WALL
+            || square == ROCK
+            || square.starts_with(REDIRECTION)
+            || square.starts_with(BREAKABLE)
+            || square == MIRROR_SLASH
+            || square == MIRROR_BACKSLASH
+            || square == SPLITTER
+            || square.starts_with(TELEPORTER)
     }
 }
 
@@ -46,6 +117,61 @@ impl Display for ObstacleType {
             ObstacleType::RedirectionDown => write!(f, "{REDIRECTION_DOWN}"),
             ObstacleType::RedirectionLeft => write!(f, "{REDIRECTION_LEFT}"),
             ObstacleType::RedirectionRight => write!(f, "{REDIRECTION_RIGHT}"),
+            // A broken obstacle leaves an empty square
+            ObstacleType::Breakable(0) => write!(f, "_"),
+            ObstacleType::Breakable(1) => write!(f, "{BREAKABLE}"),
+            ObstacleType::Breakable(hits) => write!(f, "{BREAKABLE}{hits}"),
+            ObstacleType::MirrorSlash => write!(f, "{MIRROR_SLASH}"),
+            ObstacleType::MirrorBackslash => write!(f, "{MIRROR_BACKSLASH}"),
+            ObstacleType::Splitter => write!(f, "{SPLITTER}"),
+            ObstacleType::Teleporter(id) => write!(f, "{TELEPORTER}{id}"),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_new_breakable_with_hits() {
+        assert_eq!(ObstacleType::new("X"), Ok(ObstacleType::Breakable(1)));
+        assert_eq!(ObstacleType::new("X3"), Ok(ObstacleType::Breakable(3)));
+        assert!(ObstacleType::new("X0").is_err());
+        assert!(ObstacleType::new("Xa").is_err());
+    }
+
+    #[test]
+    fn test_display_breakable() {
+        assert_eq!(ObstacleType::Breakable(1).to_string(), "X");
+        assert_eq!(ObstacleType::Breakable(2).to_string(), "X2");
+        assert_eq!(ObstacleType::Breakable(0).to_string(), "_");
+    }
+
+    #[test]
+    fn test_new_and_display_mirrors() {
+        assert_eq!(ObstacleType::new("/"), Ok(ObstacleType::MirrorSlash));
+        assert_eq!(ObstacleType::new("\\"), Ok(ObstacleType::MirrorBackslash));
+        assert_eq!(ObstacleType::MirrorSlash.to_string(), "/");
+        assert_eq!(ObstacleType::MirrorBackslash.to_string(), "\\");
+        assert!(ObstacleType::is_obstacle("/") && ObstacleType::is_obstacle("\\"));
+    }
+
+    #[test]
+    fn test_new_and_display_splitter() {
+        assert_eq!(ObstacleType::new("+"), Ok(ObstacleType::Splitter));
+        assert_eq!(ObstacleType::Splitter.to_string(), "+");
+        assert_eq!(
+            ObstacleType::from_name("Splitter"),
+            Ok(ObstacleType::Splitter)
+        );
+    }
+
+    #[test]
+    fn test_new_and_display_teleporter() {
+        assert_eq!(ObstacleType::new("T1"), Ok(ObstacleType::Teleporter(1)));
+        assert_eq!(ObstacleType::Teleporter(12).to_string(), "T12");
+        assert!(ObstacleType::new("T").is_err());
+        assert!(ObstacleType::new("Ta").is_err());
+    }
+}
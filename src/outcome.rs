@@ -0,0 +1,73 @@
+use crate::bomberman_events::BombermanEvent;
+
+// Result of a chain reaction: the final board, its events and the damage done
+#[derive(Debug, PartialEq, Clone)]
+pub struct Outcome {
+    pub board: String,
+    pub events: Vec<BombermanEvent>,
+    pub turns: u32,
+    pub kills: u32,
+    pub damage: u32,
+    pub destroyed_bombs: u32,
+    pub remaining_enemies: u32,
+}
+
+impl Outcome {
+    // Create the outcome counting the kills, damage and exploded bombs of the events
+    pub fn new(
+        board: String,
+        events: Vec<BombermanEvent>,
+        turns: u32,
+        remaining_enemies: u32,
+    ) -> Outcome {
+        let mut outcome = Outcome {
+            board,
+            events: Vec::new(),
+            turns,
+            kills: 0,
+            damage: 0,
+            destroyed_bombs: 0,
+            remaining_enemies,
+        };
+        for event in &events {
+            match event {
+                BombermanEvent::EnemyHit { .. } => outcome.damage += 1,
+                BombermanEvent::EnemyKilled(_) => {
+                    outcome.damage += 1;
+                    outcome.kills += 1;
+                }
+                BombermanEvent::BombExploded { .. } => outcome.destroyed_bombs += 1,
+                _ => (),
+            }
+        }
+        outcome.events = events;
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::point::Point;
+
+    #[test]
+    fn test_new_counts_events() {
+        let events = vec![
+            BombermanEvent::BombActivated(Point::new(0, 0)),
+            BombermanEvent::BombExploded {
+                position: Point::new(0, 0),
+                affected: vec![Point::new(0, 0), Point::new(1, 0), Point::new(0, 1)],
+            },
+            BombermanEvent::EnemyHit {
+                position: Point::new(1, 0),
+                health: 1,
+            },
+            BombermanEvent::EnemyKilled(Point::new(0, 1)),
+        ];
+        let outcome = Outcome::new("".to_string(), events, 1, 1);
+        assert_eq!(
+            (outcome.kills, outcome.damage, outcome.destroyed_bombs),
+            (1, 2, 1)
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use crate::bomberman::Bomberman;
+use crate::bomberman_errors::BombermanError;
+use crate::bomberman_events::BombermanEvent;
+use crate::utils::point::Point;
+
+// Result of a single turn of the simulation
+#[derive(Debug, PartialEq)]
+pub struct Step {
+    pub turn: u32,
+    pub bombs: Vec<Point>,
+    pub board: String,
+    pub events: Vec<BombermanEvent>,
+}
+
+// Runs the chain reaction of a game one turn at a time
+#[derive(Debug)]
+pub struct Simulation {
+    game: Bomberman,
+    pending_events: Vec<BombermanEvent>,
+}
+
+impl Simulation {
+    // Create a new simulation activating the bomb in the starting position
+    // Return an error if there is no bomb in the starting position
+    pub fn new(game: Bomberman, start_bomb: Point) -> Result<Simulation, BombermanError> {
+        match Simulation::with_start_bombs(game, &[start_bomb]) {
+            Ok(simulation) => Ok(simulation),
+            Err(mut errors) => Err(errors.remove(0)),
+        }
+    }
+
+    // Create a new simulation activating all the bombs in the starting positions,
+    // they explode together in the first turn
+    // Return an error for each starting position without a bomb
+    pub fn with_start_bombs(
+        mut game: Bomberman,
+        start_bombs: &[Point],
+    ) -> Result<Simulation, Vec<BombermanError>> {
+        let pending_events = game.activate_start_bombs(start_bombs)?;
+        Ok(Simulation {
+            game,
+            pending_events,
+        })
+    }
+
+    // Explode the bombs of the next turn
+    // Return the bombs that exploded this turn and the board after them, or None if finished
+    pub fn step(&mut self) -> Option<Step> {
+        let (bombs, explosion_events) = self.game.explode_next_turn()?;
+        let mut events = std::mem::take(&mut self.pending_events);
+        events.extend(explosion_events);
+        Some(Step {
+            turn: self.game.turn(),
+            bombs,
+            board: self.game.to_string(),
+            events,
+        })
+    }
+
+    // Return true if there are no more bombs to explode
+    pub fn is_finished(&self) -> bool {
+        !self.game.has_active_bombs()
+    }
+
+    // Return the turn of the last step, 0 before the first one
+    pub fn turn(&self) -> u32 {
+        self.game.turn()
+    }
+
+    // Return the current state of the board
+    pub fn board(&self) -> String {
+        self.game.to_string()
+    }
+
+    // Return the game in its current state, without taking it
+    pub fn game(&self) -> &Bomberman {
+        &self.game
+    }
+
+    // Return the game in its current state
+    pub fn into_game(self) -> Bomberman {
+        self.game
+    }
+}
+
+impl Iterator for Simulation {
+    type Item = Step;
+
+    fn next(&mut self) -> Option<Step> {
+        self.step()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_step_explodes_one_bomb_per_turn() {
+        let input = "B5 B2\nF2 _\n";
+        let game = Bomberman::new(input).unwrap();
+        let mut simulation = Simulation::new(game, Point::new(0, 0)).unwrap();
+
+        let first = simulation.step().unwrap();
+        assert_eq!(first.turn, 1);
+        assert_eq!(first.bombs, vec![Point::new(0, 0)]);
+        assert_eq!(first.board, "_ B2\nF1 _\n");
+        assert!(!simulation.is_finished());
+
+        let second = simulation.step().unwrap();
+        assert_eq!(second.turn, 2);
+        assert_eq!(second.bombs, vec![Point::new(1, 0)]);
+        assert_eq!(second.board, "_ _\nF1 _\n");
+        assert!(simulation.is_finished());
+        assert_eq!(simulation.step(), None);
+    }
+
+    #[test]
+    fn test_enemy_hit_state_is_reset_between_turns() {
+        let input = "B5 B5 F3\n_ _ _\n_ _ _\n";
+        let game = Bomberman::new(input).unwrap();
+        let simulation = Simulation::new(game, Point::new(0, 0)).unwrap();
+        let boards: Vec<String> = simulation.map(|step| step.board).collect();
+        assert_eq!(
+            boards,
+            vec!["_ B5 F2\n_ _ _\n_ _ _\n", "_ _ F1\n_ _ _\n_ _ _\n"]
+        );
+    }
+
+    #[test]
+    fn test_first_step_includes_start_bomb_activation() {
+        let input = "B1 _\n_ _\n";
+        let game = Bomberman::new(input).unwrap();
+        let mut simulation = Simulation::new(game, Point::new(0, 0)).unwrap();
+        let step = simulation.step().unwrap();
+        assert_eq!(
+            step.events[0],
+            BombermanEvent::BombActivated(Point::new(0, 0))
+        );
+    }
+
+    #[test]
+    fn test_start_bombs_share_the_first_step() {
+        let input = "B1 _ B1\n_ _ _\n_ _ _\n";
+        let game = Bomberman::new(input).unwrap();
+        let mut simulation =
+            Simulation::with_start_bombs(game, &[Point::new(0, 0), Point::new(2, 0)]).unwrap();
+        let step = simulation.step().unwrap();
+        assert_eq!(step.bombs, vec![Point::new(0, 0), Point::new(2, 0)]);
+        assert!(simulation.is_finished());
+    }
+
+    #[test]
+    fn test_step_turn_follows_the_fuses() {
+        let input = "B1 B1:3\n_ _\n";
+        let game = Bomberman::new(input).unwrap();
+        let turns: Vec<u32> = Simulation::new(game, Point::new(0, 0))
+            .unwrap()
+            .map(|step| step.turn)
+            .collect();
+        assert_eq!(turns, vec![1, 4]);
+    }
+
+    #[test]
+    fn test_no_bomb_in_start_position_returns_error() {
+        let game = Bomberman::new("_ _\n_ _\n").unwrap();
+        let result = Simulation::new(game, Point::new(0, 0));
+        assert!(result.is_err());
+    }
+}
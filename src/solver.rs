@@ -0,0 +1,97 @@
+use crate::bomberman::Bomberman;
+use crate::utils::point::Point;
+use std::cmp::Reverse;
+
+// Result of playing a game from one starting bomb
+#[derive(Debug, PartialEq, Clone)]
+pub struct Solution {
+    pub start_bomb: Point,
+    pub kills: u32,
+    pub damage: u32,
+    pub destroyed_bombs: u32,
+    pub remaining_enemies: u32,
+}
+
+// Play a copy of the game from every bomb that has not exploded, the game is not changed
+// Returns the solutions ranked by kills, then destroyed bombs, then total damage
+// Ties keep the order of the starting bombs in the maze
+pub fn solve(game: &Bomberman) -> Vec<Solution> {
+    let mut solutions: Vec<Solution> = game
+        .bomb_positions()
+        .into_iter()
+        .filter_map(|start_bomb| evaluate(game, start_bomb))
+        .collect();
+    solutions.sort_by_key(|solution| {
+        Reverse((solution.kills, solution.destroyed_bombs, solution.damage))
+    });
+    solutions
+}
+
+// Play a copy of the game from the starting bomb and count its results
+fn evaluate(game: &Bomberman, start_bomb: Point) -> Option<Solution> {
+    let outcome = game.simulate(start_bomb).ok()?;
+    Some(Solution {
+        start_bomb,
+        kills: outcome.kills,
+        damage: outcome.damage,
+        destroyed_bombs: outcome.destroyed_bombs,
+        remaining_enemies: outcome.remaining_enemies,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_solve_ranks_by_kills() {
+        let input = "B1 _ _\n_ _ _\nF1 B1 _\n";
+        let game = Bomberman::new(input).unwrap();
+        let solutions = solve(&game);
+        assert_eq!(
+            solutions,
+            vec![
+                Solution {
+                    start_bomb: Point::new(1, 2),
+                    kills: 1,
+                    damage: 1,
+                    destroyed_bombs: 1,
+                    remaining_enemies: 0,
+                },
+                Solution {
+                    start_bomb: Point::new(0, 0),
+                    kills: 0,
+                    damage: 0,
+                    destroyed_bombs: 1,
+                    remaining_enemies: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_solve_breaks_ties_with_destroyed_bombs() {
+        let input = "B1 F1 B1\n_ _ B1\n_ _ _\n";
+        let game = Bomberman::new(input).unwrap();
+        let starts: Vec<(Point, u32)> = solve(&game)
+            .iter()
+            .map(|solution| (solution.start_bomb, solution.destroyed_bombs))
+            .collect();
+        assert_eq!(
+            starts,
+            vec![
+                (Point::new(2, 0), 2),
+                (Point::new(2, 1), 2),
+                (Point::new(0, 0), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_solve_does_not_change_the_game() {
+        let input = "B1 F1\n_ _\n";
+        let game = Bomberman::new(input).unwrap();
+        solve(&game);
+        assert_eq!(game.to_string(), input);
+    }
+}
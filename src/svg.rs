@@ -0,0 +1,304 @@
+use crate::bomberman_events::BombermanEvent;
+use crate::maze_placeable::bomb::FUSE_SEPARATOR;
+use crate::maze_placeable::bomb_type::{NORMAL_BOMB, PENETRATING_BOMB};
+use crate::maze_placeable::enemy::ENEMY;
+use crate::maze_placeable::obstacle_type::{
+    ObstacleType, BREAKABLE, MIRROR_BACKSLASH, MIRROR_SLASH, REDIRECTION_DOWN, REDIRECTION_LEFT,
+    REDIRECTION_RIGHT, REDIRECTION_UP, ROCK, SPLITTER, TELEPORTER, WALL,
+};
+use crate::utils::maze_display::MazeDisplay;
+use crate::utils::point::Point;
+
+// Size in pixels of each square of the maze
+pub const CELL_SIZE: u32 = 40;
+
+const BACKGROUND_COLOR: &str = "#f4f1e8";
+const GRID_COLOR: &str = "#d8d2c0";
+const WALL_COLOR: &str = "#6b6b6b";
+const ROCK_COLOR: &str = "#8b5a2b";
+const BREAKABLE_COLOR: &str = "#c8964e";
+const REDIRECTION_COLOR: &str = "#2f6fb0";
+const TELEPORTER_COLOR: &str = "#8e44ad";
+const NORMAL_BOMB_COLOR: &str = "#222222";
+const PENETRATING_BOMB_COLOR: &str = "#b02222";
+const ENEMY_COLOR: &str = "#2e8b3a";
+const BLAST_COLOR: &str = "#ff8c00";
+
+// Render a width x height maze as an SVG image
+// Each object is drawn from its MazeDisplay token, empty squares are left blank:
+//  - Walls are grey squares and rocks brown rounded squares
+//  - Breakable blocks are light brown squares with a crack, and their hits if more than one
+//  - Redirections are arrows pointing to their direction
+//  - Mirrors are blue diagonal lines with the slope of their token
+//  - Splitters are blue crosses
+//  - Teleporters are purple rings with their id
+//  - Bombs are circles with their range, penetrating bombs in red. Fuses are shown after the range
+//  - Enemies are green circles with one pip for each point of health
+// The highlighted squares are overlaid in orange, eg: the squares hit by an explosion
+pub fn render(
+    width: u32,
+    height: u32,
+    objects: &[&dyn MazeDisplay],
+    highlighted: &[Point],
+) -> String {
+    let (image_width, image_height) = (width * CELL_SIZE, height * CELL_SIZE);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{image_width}\" height=\"{image_height}\" viewBox=\"0 0 {image_width} {image_height}\">\n"
+    );
+    svg.push_str(&format!(
+        "<rect width=\"{image_width}\" height=\"{image_height}\" fill=\"{BACKGROUND_COLOR}\"/>\n"
+    ));
+    for x in 1..width {
+        svg.push_str(&format!(
+            "<line x1=\"{0}\" y1=\"0\" x2=\"{0}\" y2=\"{image_height}\" stroke=\"{GRID_COLOR}\"/>\n",
+            x * CELL_SIZE
+        ));
+    }
+    for y in 1..height {
+        svg.push_str(&format!(
+            "<line x1=\"0\" y1=\"{0}\" x2=\"{image_width}\" y2=\"{0}\" stroke=\"{GRID_COLOR}\"/>\n",
+            y * CELL_SIZE
+        ));
+    }
+    for object in objects {
+        svg.push_str(&render_square(&object.display(), object.get_position()));
+    }
+    for point in highlighted {
+        svg.push_str(&format!(
+            "<rect class=\"blast\" x=\"{}\" y=\"{}\" width=\"{CELL_SIZE}\" height=\"{CELL_SIZE}\" fill=\"{BLAST_COLOR}\" fill-opacity=\"0.4\"/>\n",
+            point.x * CELL_SIZE,
+            point.y * CELL_SIZE
+        ));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+// Return every square affected by the explosions of the events, sorted and without repeats
+pub fn blast_squares(events: &[BombermanEvent]) -> Vec<Point> {
+    let mut squares: Vec<Point> = events
+        .iter()
+        .filter_map(|event| match event {
+            BombermanEvent::BombExploded { affected, .. } => Some(affected),
+            _ => None,
+        })
+        .flatten()
+        .copied()
+        .collect();
+    squares.sort();
+    squares.dedup();
+    squares
+}
+
+// Render the object of a square from its token
+// Unknown tokens and empty squares are not drawn
+fn render_square(token: &str, position: Point) -> String {
+    let (x, y) = (position.x * CELL_SIZE, position.y * CELL_SIZE);
+    let (center_x, center_y) = (x + CELL_SIZE / 2, y + CELL_SIZE / 2);
+    match token {
+        WALL => format!(
+            "<rect class=\"wall\" x=\"{x}\" y=\"{y}\" width=\"{CELL_SIZE}\" height=\"{CELL_SIZE}\" fill=\"{WALL_COLOR}\"/>\n"
+        ),
+        ROCK => format!(
+            "<rect class=\"rock\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"8\" fill=\"{ROCK_COLOR}\"/>\n",
+            x + 3,
+            y + 3,
+            CELL_SIZE - 6,
+            CELL_SIZE - 6
+        ),
+        REDIRECTION_UP | REDIRECTION_DOWN | REDIRECTION_LEFT | REDIRECTION_RIGHT => {
+            render_arrow(token, center_x, center_y)
+        }
+        MIRROR_SLASH | MIRROR_BACKSLASH => {
+            // Slash goes from the bottom left to the top right corner
+            let (start_y, end_y) = if token == MIRROR_SLASH {
+                (y + CELL_SIZE - 4, y + 4)
+            } else {
+                (y + 4, y + CELL_SIZE - 4)
+            };
+            format!(
+                "<line class=\"mirror\" x1=\"{}\" y1=\"{start_y}\" x2=\"{}\" y2=\"{end_y}\" stroke=\"{REDIRECTION_COLOR}\" stroke-width=\"4\"/>\n",
+                x + 4,
+                x + CELL_SIZE - 4
+            )
+        }
+        SPLITTER => format!(
+            "<path class=\"splitter\" d=\"M{} {center_y} H{} M{center_x} {} V{}\" stroke=\"{REDIRECTION_COLOR}\" stroke-width=\"4\"/>\n",
+            x + 6,
+            x + CELL_SIZE - 6,
+            y + 6,
+            y + CELL_SIZE - 6
+        ),
+        _ if token.starts_with(TELEPORTER) => format!(
+            "<circle class=\"teleporter\" cx=\"{center_x}\" cy=\"{center_y}\" r=\"{}\" fill=\"none\" stroke=\"{TELEPORTER_COLOR}\" stroke-width=\"4\"/>\n<text x=\"{center_x}\" y=\"{}\" font-family=\"sans-serif\" font-size=\"12\" text-anchor=\"middle\" fill=\"{TELEPORTER_COLOR}\">{}</text>\n",
+            CELL_SIZE * 2 / 5,
+            center_y + 4,
+            &token[TELEPORTER.len()..]
+        ),
+        _ if token.starts_with(BREAKABLE) => {
+            let mut block = format!(
+                "<rect class=\"breakable\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{BREAKABLE_COLOR}\"/>\n<polyline points=\"{},{} {},{} {},{} {},{}\" fill=\"none\" stroke=\"{ROCK_COLOR}\" stroke-width=\"2\"/>\n",
+                x + 2,
+                y + 2,
+                CELL_SIZE - 4,
+                CELL_SIZE - 4,
+                x + 8,
+                y + 6,
+                center_x - 2,
+                center_y - 4,
+                center_x + 4,
+                center_y + 4,
+                x + CELL_SIZE - 8,
+                y + CELL_SIZE - 6
+            );
+            let hits = &token[BREAKABLE.len()..];
+            if !hits.is_empty() {
+                block.push_str(&format!(
+                    "<text x=\"{}\" y=\"{}\" font-family=\"sans-serif\" font-size=\"12\" fill=\"#ffffff\">{hits}</text>\n",
+                    x + 5,
+                    y + CELL_SIZE - 6
+                ));
+            }
+            block
+        }
+        _ if token.starts_with(NORMAL_BOMB) || token.starts_with(PENETRATING_BOMB) => {
+            let color = if token.starts_with(PENETRATING_BOMB) {
+                PENETRATING_BOMB_COLOR
+            } else {
+                NORMAL_BOMB_COLOR
+            };
+            let label = token[1..].replace(FUSE_SEPARATOR, "·");
+            format!(
+                "<circle class=\"bomb\" cx=\"{center_x}\" cy=\"{center_y}\" r=\"{}\" fill=\"{color}\"/>\n<text x=\"{center_x}\" y=\"{}\" font-family=\"sans-serif\" font-size=\"14\" text-anchor=\"middle\" fill=\"#ffffff\">{label}</text>\n",
+                CELL_SIZE * 2 / 5,
+                center_y + 5
+            )
+        }
+        _ if token.starts_with(ENEMY) => {
+            let health = token[1..].parse::<u32>().unwrap_or(0);
+            let mut enemy = format!(
+                "<circle class=\"enemy\" cx=\"{center_x}\" cy=\"{}\" r=\"{}\" fill=\"{ENEMY_COLOR}\"/>\n",
+                center_y - 4,
+                CELL_SIZE * 3 / 10
+            );
+            // Pips are centered in a row under the enemy
+            let first_pip = center_x as i64 - 4 * (health as i64 - 1);
+            for pip in 0..health as i64 {
+                enemy.push_str(&format!(
+                    "<circle class=\"health\" cx=\"{}\" cy=\"{}\" r=\"3\" fill=\"{ENEMY_COLOR}\"/>\n",
+                    first_pip + 8 * pip,
+                    y + CELL_SIZE - 5
+                ));
+            }
+            enemy
+        }
+        _ => String::new(),
+    }
+}
+
+// Render a redirection as an arrow pointing to its direction
+fn render_arrow(token: &str, center_x: u32, center_y: u32) -> String {
+    let rotation = match ObstacleType::new(token) {
+        Ok(ObstacleType::RedirectionRight) => 90,
+        Ok(ObstacleType::RedirectionDown) => 180,
+        Ok(ObstacleType::RedirectionLeft) => 270,
+        _ => 0,
+    };
+    format!(
+        "<polygon class=\"redirection\" points=\"{center_x},{} {},{} {},{}\" fill=\"{REDIRECTION_COLOR}\" transform=\"rotate({rotation} {center_x} {center_y})\"/>\n",
+        center_y - 14,
+        center_x + 12,
+        center_y + 10,
+        center_x - 12,
+        center_y + 10
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::maze_placeable::bomb::Bomb;
+    use crate::maze_placeable::enemy::Enemy;
+    use crate::maze_placeable::obstacle::Obstacle;
+
+    #[test]
+    fn test_render_draws_each_object() {
+        let wall = Obstacle::new("W", Point::new(0, 0)).unwrap();
+        let arrow = Obstacle::new("DL", Point::new(1, 0)).unwrap();
+        let bomb = Bomb::new("S3:2", Point::new(0, 1)).unwrap();
+        let enemy = Enemy::new("F2", Point::new(1, 1)).unwrap();
+        let objects: Vec<&dyn MazeDisplay> = vec![&wall, &arrow, &bomb, &enemy];
+        let svg = render(2, 2, &objects, &[]);
+
+        assert!(
+            svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"80\" height=\"80\"")
+        );
+        assert!(svg.contains("class=\"wall\" x=\"0\" y=\"0\""));
+        assert!(svg.contains("rotate(270 60 20)"));
+        assert!(svg.contains(&format!("fill=\"{PENETRATING_BOMB_COLOR}\"")));
+        assert!(svg.contains(">3·2</text>"));
+        assert_eq!(svg.matches("class=\"health\"").count(), 2);
+        assert!(!svg.contains("class=\"blast\""));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn test_render_breakable_shows_its_hits() {
+        let block = Obstacle::new("X", Point::new(0, 0)).unwrap();
+        let strong_block = Obstacle::new("X3", Point::new(1, 0)).unwrap();
+        let svg = render(2, 1, &[&block, &strong_block], &[]);
+        assert_eq!(svg.matches("class=\"breakable\"").count(), 2);
+        assert!(svg.contains(">3</text>"));
+        assert_eq!(svg.matches("</text>").count(), 1);
+    }
+
+    #[test]
+    fn test_render_mirrors_with_their_slope() {
+        let slash = Obstacle::new("/", Point::new(0, 0)).unwrap();
+        let backslash = Obstacle::new("\\", Point::new(1, 0)).unwrap();
+        let svg = render(2, 1, &[&slash, &backslash], &[]);
+        assert!(svg.contains("x1=\"4\" y1=\"36\" x2=\"36\" y2=\"4\""));
+        assert!(svg.contains("x1=\"44\" y1=\"4\" x2=\"76\" y2=\"36\""));
+    }
+
+    #[test]
+    fn test_render_splitter_as_cross() {
+        let splitter = Obstacle::new("+", Point::new(0, 0)).unwrap();
+        let svg = render(1, 1, &[&splitter], &[]);
+        assert!(svg.contains("d=\"M6 20 H34 M20 6 V34\""));
+    }
+
+    #[test]
+    fn test_render_teleporter_with_its_id() {
+        let teleporter = Obstacle::new("T7", Point::new(0, 0)).unwrap();
+        let svg = render(1, 1, &[&teleporter], &[]);
+        assert!(svg.contains("class=\"teleporter\""));
+        assert!(svg.contains(">7</text>"));
+    }
+
+    #[test]
+    fn test_render_overlays_highlighted_squares() {
+        let svg = render(2, 1, &[], &[Point::new(1, 0)]);
+        assert_eq!(svg.matches("class=\"blast\"").count(), 1);
+        assert!(svg.contains("class=\"blast\" x=\"40\" y=\"0\""));
+    }
+
+    #[test]
+    fn test_blast_squares_merges_explosions() {
+        let events = vec![
+            BombermanEvent::BombExploded {
+                position: Point::new(0, 0),
+                affected: vec![Point::new(0, 0), Point::new(1, 0)],
+            },
+            BombermanEvent::EnemyKilled(Point::new(1, 0)),
+            BombermanEvent::BombExploded {
+                position: Point::new(1, 1),
+                affected: vec![Point::new(1, 0), Point::new(1, 1)],
+            },
+        ];
+        assert_eq!(
+            blast_squares(&events),
+            vec![Point::new(0, 0), Point::new(1, 0), Point::new(1, 1)]
+        );
+    }
+}
@@ -1,8 +1,10 @@
+use crate::bomberman_events::BombermanEvent;
 use crate::utils::point::Point;
 
 pub trait CanBeHit {
     // Hit the object so it changes its state if needed
-    fn hit(&mut self);
+    // Return the event produced by the hit, if the state changed
+    fn hit(&mut self) -> Option<BombermanEvent>;
     // Return the position of the object
     fn in_position(&self, position: Point) -> bool;
 }
@@ -0,0 +1,169 @@
+use crate::maze_placeable::obstacle::Obstacle;
+use crate::maze_placeable::obstacle_type::ObstacleType;
+use crate::utils::point::Point;
+use std::collections::{BTreeMap, HashMap};
+
+// Index of the object in a square, pointing into the vector that holds it
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Occupant {
+    Enemy(usize),
+    Bomb(usize),
+    Obstacle(usize),
+}
+
+// Spatial index from each square of the maze to the object in it
+#[derive(Debug, PartialEq, Clone)]
+pub struct Grid {
+    width: u32,
+    height: u32,
+    cells: Vec<Option<Occupant>>,
+    // Index of the other teleporter with the same id, for each paired teleporter
+    partners: HashMap<usize, usize>,
+}
+
+impl Grid {
+    // Create an empty grid of width x height squares
+    pub fn new(width: u32, height: u32) -> Grid {
+        Grid {
+            width,
+            height,
+            cells: vec![None; width as usize * height as usize],
+            partners: HashMap::new(),
+        }
+    }
+
+    // Create a grid indexing only the given obstacles, with their teleporters paired
+    pub fn from_obstacles(width: u32, height: u32, obstacles: &[Obstacle]) -> Grid {
+        let mut grid = Grid::new(width, height);
+        for (index, obstacle) in obstacles.iter().enumerate() {
+            grid.insert(obstacle.position, Occupant::Obstacle(index));
+        }
+        grid.pair_teleporters(obstacles);
+        grid
+    }
+
+    // Return the number of columns of the grid
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    // Return the number of lines of the grid
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    // Return the occupant of the position, or None if it is empty or out of bounds
+    pub fn get(&self, position: Point) -> Option<Occupant> {
+        self.index(position).and_then(|index| self.cells[index])
+    }
+
+    // Set the occupant of the position, positions out of bounds are ignored
+    pub fn insert(&mut self, position: Point, occupant: Occupant) {
+        if let Some(index) = self.index(position) {
+            self.cells[index] = Some(occupant);
+        }
+    }
+
+    // Return the index of the obstacle in the position, if there is one
+    pub fn obstacle_at(&self, position: Point) -> Option<usize> {
+        match self.get(position) {
+            Some(Occupant::Obstacle(index)) => Some(index),
+            _ => None,
+        }
+    }
+
+    // Link each teleporter to the other one with the same id, so an explosion finds
+    // where it goes out without looking through the obstacles
+    // Ids that do not appear exactly twice are not linked
+    // Return the index of the teleporters of each id, in the order of the ids
+    pub fn pair_teleporters(&mut self, obstacles: &[Obstacle]) -> BTreeMap<u32, Vec<usize>> {
+        let mut teleporters: BTreeMap<u32, Vec<usize>> = BTreeMap::new();
+        for (index, obstacle) in obstacles.iter().enumerate() {
+            if let ObstacleType::Teleporter(id) = obstacle.obstacle_type {
+                teleporters.entry(id).or_default().push(index);
+            }
+        }
+        self.partners.clear();
+        for indexes in teleporters.values() {
+            if let [first, second] = indexes[..] {
+                self.partners.insert(first, second);
+                self.partners.insert(second, first);
+            }
+        }
+        teleporters
+    }
+
+    // Return the index of the other teleporter with the same id, if the obstacle is
+    // a paired teleporter
+    pub fn partner(&self, obstacle: usize) -> Option<usize> {
+        self.partners.get(&obstacle).copied()
+    }
+
+    fn index(&self, position: Point) -> Option<usize> {
+        if position.x >= self.width || position.y >= self.height {
+            return None;
+        }
+        Some(position.y as usize * self.width as usize + position.x as usize)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_get_returns_inserted_occupant() {
+        let mut grid = Grid::new(3, 2);
+        grid.insert(Point::new(2, 1), Occupant::Bomb(4));
+        assert_eq!(grid.get(Point::new(2, 1)), Some(Occupant::Bomb(4)));
+        assert_eq!(grid.get(Point::new(1, 2)), None);
+    }
+
+    #[test]
+    fn test_get_out_of_bounds_is_empty() {
+        let mut grid = Grid::new(2, 2);
+        grid.insert(Point::new(5, 0), Occupant::Enemy(0));
+        assert_eq!(grid.get(Point::new(5, 0)), None);
+    }
+
+    #[test]
+    fn test_from_obstacles_indexes_obstacles() {
+        let obstacles = vec![
+            Obstacle::new("W", Point::new(0, 0)).unwrap(),
+            Obstacle::new("R", Point::new(1, 1)).unwrap(),
+        ];
+        let grid = Grid::from_obstacles(2, 2, &obstacles);
+        assert_eq!(grid.obstacle_at(Point::new(1, 1)), Some(1));
+        assert_eq!(grid.obstacle_at(Point::new(1, 0)), None);
+    }
+
+    #[test]
+    fn test_from_obstacles_pairs_teleporters() {
+        let obstacles = vec![
+            Obstacle::new("T1", Point::new(0, 0)).unwrap(),
+            Obstacle::new("T2", Point::new(1, 0)).unwrap(),
+            Obstacle::new("T1", Point::new(2, 0)).unwrap(),
+        ];
+        let grid = Grid::from_obstacles(3, 1, &obstacles);
+        assert_eq!(grid.partner(0), Some(2));
+        assert_eq!(grid.partner(2), Some(0));
+        assert_eq!(grid.partner(1), None);
+    }
+
+    #[test]
+    fn test_pair_teleporters_returns_teleporters_by_id() {
+        let obstacles = vec![
+            Obstacle::new("T3", Point::new(0, 0)).unwrap(),
+            Obstacle::new("W", Point::new(1, 0)).unwrap(),
+            Obstacle::new("T1", Point::new(2, 0)).unwrap(),
+            Obstacle::new("T3", Point::new(3, 0)).unwrap(),
+        ];
+        let mut grid = Grid::new(4, 1);
+        assert_eq!(
+            grid.pair_teleporters(&obstacles),
+            BTreeMap::from([(1, vec![2]), (3, vec![0, 3])])
+        );
+        assert_eq!(grid.partner(2), None);
+        assert_eq!(grid.partner(3), Some(0));
+    }
+}
@@ -0,0 +1,409 @@
+use std::fmt::Display;
+
+// Minimal JSON value, enough to import and export mazes without external crates
+// Object keys keep the order they were written in
+#[derive(Debug, PartialEq, Clone)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    // Parse a JSON document
+    // Return an error with the byte offset if the document is invalid
+    pub fn parse(input: &str) -> Result<JsonValue, String> {
+        let mut parser = Parser {
+            input: input.as_bytes(),
+            position: 0,
+            depth: 0,
+        };
+        let value = parser.parse_value()?;
+        parser.skip_whitespace();
+        if parser.position != parser.input.len() {
+            return Err(parser.error("unexpected trailing characters"));
+        }
+        Ok(value)
+    }
+
+    // Return the value of the key if this is an object that has it
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(fields) => fields
+                .iter()
+                .find(|(field, _)| field == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    // Return the number as u32 if this is a non negative integer that fits
+    pub fn as_u32(&self) -> Option<u32> {
+        match self {
+            JsonValue::Number(n) if n.fract() == 0.0 && *n >= 0.0 && *n <= u32::MAX as f64 => {
+                Some(*n as u32)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[JsonValue]> {
+        match self {
+            JsonValue::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    // Write the value indented with two spaces per level
+    pub fn to_pretty_string(&self) -> String {
+        let mut out = String::new();
+        self.write_pretty(&mut out, 0);
+        out
+    }
+
+    fn write_pretty(&self, out: &mut String, level: usize) {
+        let indent = "  ".repeat(level + 1);
+        match self {
+            JsonValue::Array(values) if !values.is_empty() => {
+                out.push_str("[\n");
+                for (i, value) in values.iter().enumerate() {
+                    out.push_str(&indent);
+                    value.write_pretty(out, level + 1);
+                    out.push_str(if i + 1 < values.len() { ",\n" } else { "\n" });
+                }
+                out.push_str(&"  ".repeat(level));
+                out.push(']');
+            }
+            JsonValue::Object(fields) if !fields.is_empty() => {
+                out.push_str("{\n");
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    out.push_str(&indent);
+                    out.push_str(&format!("{}: ", JsonValue::String(key.clone())));
+                    value.write_pretty(out, level + 1);
+                    out.push_str(if i + 1 < fields.len() { ",\n" } else { "\n" });
+                }
+                out.push_str(&"  ".repeat(level));
+                out.push('}');
+            }
+            _ => out.push_str(&self.to_string()),
+        }
+    }
+}
+
+impl Display for JsonValue {
+    // Write the value in a single line
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            JsonValue::Null => write!(f, "null"),
+            JsonValue::Bool(b) => write!(f, "{b}"),
+            JsonValue::Number(n) => write!(f, "{n}"),
+            JsonValue::String(s) => {
+                write!(f, "\"")?;
+                for c in s.chars() {
+                    match c {
+                        '"' => write!(f, "\\\"")?,
+                        '\\' => write!(f, "\\\\")?,
+                        '\n' => write!(f, "\\n")?,
+                        '\r' => write!(f, "\\r")?,
+                        '\t' => write!(f, "\\t")?,
+                        c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+                        c => write!(f, "{c}")?,
+                    }
+                }
+                write!(f, "\"")
+            }
+            JsonValue::Array(values) => {
+                let values: Vec<String> = values.iter().map(JsonValue::to_string).collect();
+                write!(f, "[{}]", values.join(","))
+            }
+            JsonValue::Object(fields) => {
+                let fields: Vec<String> = fields
+                    .iter()
+                    .map(|(key, value)| format!("{}:{value}", JsonValue::String(key.clone())))
+                    .collect();
+                write!(f, "{{{}}}", fields.join(","))
+            }
+        }
+    }
+}
+
+// Deepest nesting of objects and arrays a document can have, the parser calls itself
+// for each level so deeper documents are an error instead of overflowing the stack
+const MAX_DEPTH: usize = 128;
+
+struct Parser<'a> {
+    input: &'a [u8],
+    position: usize,
+    // Number of objects and arrays the parser is inside of
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &str) -> String {
+        format!("{message} at byte {}", self.position)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\n' | b'\r' | b'\t') = self.input.get(self.position) {
+            self.position += 1;
+        }
+    }
+
+    fn expect(&mut self, literal: &str) -> Result<(), String> {
+        if self.input[self.position..].starts_with(literal.as_bytes()) {
+            self.position += literal.len();
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected {literal}")))
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<JsonValue, String> {
+        self.skip_whitespace();
+        match self.input.get(self.position) {
+            Some(b'{' | b'[') if self.depth == MAX_DEPTH => {
+                Err(self.error(&format!("more than {MAX_DEPTH} nested objects and arrays")))
+            }
+            Some(b'{') => self.nested(Parser::parse_object),
+            Some(b'[') => self.nested(Parser::parse_array),
+            Some(b'"') => Ok(JsonValue::String(self.parse_string()?)),
+            Some(b't') => self.expect("true").map(|_| JsonValue::Bool(true)),
+            Some(b'f') => self.expect("false").map(|_| JsonValue::Bool(false)),
+            Some(b'n') => self.expect("null").map(|_| JsonValue::Null),
+            Some(b'-' | b'0'..=b'9') => self.parse_number(),
+            Some(_) => Err(self.error("unexpected character")),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    // Parse an object or an array one level deeper
+    fn nested(
+        &mut self,
+        parse: fn(&mut Self) -> Result<JsonValue, String>,
+    ) -> Result<JsonValue, String> {
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn parse_object(&mut self) -> Result<JsonValue, String> {
+        self.expect("{")?;
+        let mut fields = Vec::new();
+        self.skip_whitespace();
+        if self.input.get(self.position) == Some(&b'}') {
+            self.position += 1;
+            return Ok(JsonValue::Object(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.parse_string()?;
+            self.skip_whitespace();
+            self.expect(":")?;
+            let value = self.parse_value()?;
+            fields.push((key, value));
+            self.skip_whitespace();
+            match self.input.get(self.position) {
+                Some(b',') => self.position += 1,
+                Some(b'}') => {
+                    self.position += 1;
+                    return Ok(JsonValue::Object(fields));
+                }
+                _ => return Err(self.error("expected , or }")),
+            }
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<JsonValue, String> {
+        self.expect("[")?;
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if self.input.get(self.position) == Some(&b']') {
+            self.position += 1;
+            return Ok(JsonValue::Array(values));
+        }
+        loop {
+            values.push(self.parse_value()?);
+            self.skip_whitespace();
+            match self.input.get(self.position) {
+                Some(b',') => self.position += 1,
+                Some(b']') => {
+                    self.position += 1;
+                    return Ok(JsonValue::Array(values));
+                }
+                _ => return Err(self.error("expected , or ]")),
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, String> {
+        self.expect("\"")?;
+        let mut bytes = Vec::new();
+        loop {
+            let Some(&byte) = self.input.get(self.position) else {
+                return Err(self.error("unterminated string"));
+            };
+            self.position += 1;
+            match byte {
+                b'"' => break,
+                b'\\' => {
+                    let Some(&escaped) = self.input.get(self.position) else {
+                        return Err(self.error("unterminated string"));
+                    };
+                    self.position += 1;
+                    let c = match escaped {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => self.parse_unicode_escape()?,
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    let mut buffer = [0; 4];
+                    bytes.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+                }
+                byte => bytes.push(byte),
+            }
+        }
+        String::from_utf8(bytes).map_err(|_| self.error("invalid utf-8 in string"))
+    }
+
+    // Characters outside the basic plane are escaped as a pair of surrogates,
+    // a high one followed by a low one. Eg: \ud83d\ude00
+    fn parse_unicode_escape(&mut self) -> Result<char, String> {
+        let code = match self.parse_hex4() {
+            Some(high @ 0xD800..=0xDBFF) if self.input[self.position..].starts_with(b"\\u") => {
+                self.position += 2;
+                match self.parse_hex4() {
+                    Some(low @ 0xDC00..=0xDFFF) => {
+                        Some(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))
+                    }
+                    _ => None,
+                }
+            }
+            code => code,
+        };
+        code.and_then(char::from_u32)
+            .ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    // Read the 4 hex digits of a unicode escape
+    fn parse_hex4(&mut self) -> Option<u32> {
+        let hex = self
+            .input
+            .get(self.position..self.position + 4)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u32::from_str_radix(hex, 16).ok());
+        self.position += 4;
+        hex
+    }
+
+    fn parse_number(&mut self) -> Result<JsonValue, String> {
+        let start = self.position;
+        while let Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9') =
+            self.input.get(self.position)
+        {
+            self.position += 1;
+        }
+        std::str::from_utf8(&self.input[start..self.position])
+            .ok()
+            .and_then(|number| number.parse::<f64>().ok())
+            .map(JsonValue::Number)
+            .ok_or_else(|| self.error("invalid number"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_object() {
+        let value = JsonValue::parse(r#"{"a": [1, 2.5, true], "b": {"c": null}, "d": "x\"y"}"#);
+        assert_eq!(
+            value,
+            Ok(JsonValue::Object(vec![
+                (
+                    "a".to_string(),
+                    JsonValue::Array(vec![
+                        JsonValue::Number(1.0),
+                        JsonValue::Number(2.5),
+                        JsonValue::Bool(true),
+                    ])
+                ),
+                (
+                    "b".to_string(),
+                    JsonValue::Object(vec![("c".to_string(), JsonValue::Null)])
+                ),
+                ("d".to_string(), JsonValue::String("x\"y".to_string())),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_returns_error_with_position() {
+        assert_eq!(
+            JsonValue::parse("[1, 2"),
+            Err("expected , or ] at byte 5".to_string())
+        );
+        assert_eq!(
+            JsonValue::parse("{} x"),
+            Err("unexpected trailing characters at byte 3".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_too_deeply_nested_is_error() {
+        assert!(JsonValue::parse(&"[".repeat(200000)).is_err());
+        let objects = r#"{"a":"#.repeat(MAX_DEPTH + 1) + "1" + &"}".repeat(MAX_DEPTH + 1);
+        assert_eq!(
+            JsonValue::parse(&objects),
+            Err(format!(
+                "more than {MAX_DEPTH} nested objects and arrays at byte {}",
+                5 * MAX_DEPTH
+            ))
+        );
+        let arrays = "[".repeat(MAX_DEPTH) + &"]".repeat(MAX_DEPTH);
+        assert!(JsonValue::parse(&arrays).is_ok());
+    }
+
+    #[test]
+    fn test_parse_unicode_escapes() {
+        assert_eq!(
+            JsonValue::parse(r#""\u00e9\ud83d\ude00""#),
+            Ok(JsonValue::String("\u{e9}\u{1f600}".to_string()))
+        );
+        assert!(JsonValue::parse(r#""\ud83d""#).is_err());
+        assert!(JsonValue::parse(r#""\ud83d\u0041""#).is_err());
+        assert!(JsonValue::parse(r#""\ude00""#).is_err());
+    }
+
+    #[test]
+    fn test_display_round_trips() {
+        let input = r#"{"name":"a\nb","values":[1,-2,3.5],"empty":{}}"#;
+        let value = JsonValue::parse(input).unwrap();
+        assert_eq!(value.to_string(), input);
+        assert_eq!(JsonValue::parse(&value.to_pretty_string()), Ok(value));
+    }
+
+    #[test]
+    fn test_as_u32() {
+        assert_eq!(JsonValue::Number(3.0).as_u32(), Some(3));
+        assert_eq!(JsonValue::Number(-1.0).as_u32(), None);
+        assert_eq!(JsonValue::Number(1.5).as_u32(), None);
+    }
+}
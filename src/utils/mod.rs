@@ -1,4 +1,6 @@
 pub mod can_be_hit;
 pub mod direction;
+pub mod grid;
+pub mod json;
 pub mod maze_display;
 pub mod point;
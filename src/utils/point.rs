@@ -13,12 +13,19 @@ impl Point {
         Point { x, y }
     }
 
-    pub fn next_point(self, direction: Direction, limit: u32) -> Result<Point, String> {
+    // Return the next point in the given direction inside a board of width x height
+    // Each axis is checked against its own limit
+    pub fn next_point(
+        self,
+        direction: Direction,
+        width: u32,
+        height: u32,
+    ) -> Result<Point, String> {
         let point = match direction {
-            Direction::Down if self.y < limit - 1 => Point::new(self.x, self.y + 1),
+            Direction::Down if self.y + 1 < height => Point::new(self.x, self.y + 1),
             Direction::Up if self.y > 0 => Point::new(self.x, self.y - 1),
             Direction::Left if self.x > 0 => Point::new(self.x - 1, self.y),
-            Direction::Right if self.x < limit - 1 => Point::new(self.x + 1, self.y),
+            Direction::Right if self.x + 1 < width => Point::new(self.x + 1, self.y),
             _ => {
                 return Err(format!(
                     "Cannot move {:?} from the {self} of the board, it goes out of bounds",
@@ -44,7 +51,7 @@ mod tests {
     fn test_next_point_up_from_start_throw_error() {
         let point = Point::new(0, 0);
         let direction = Direction::Up;
-        let result = point.next_point(direction, 2);
+        let result = point.next_point(direction, 2, 2);
         assert_eq!(
             result,
             Err(format!(
@@ -58,7 +65,7 @@ mod tests {
     fn test_next_point_left_from_start_throw_error() {
         let point = Point::new(0, 0);
         let direction = Direction::Left;
-        let result = point.next_point(direction, 2);
+        let result = point.next_point(direction, 2, 2);
         assert_eq!(
             result,
             Err(format!(
@@ -72,7 +79,7 @@ mod tests {
     fn test_next_point_down_from_start() {
         let point = Point::new(0, 0);
         let direction = Direction::Down;
-        let result = point.next_point(direction, 2);
+        let result = point.next_point(direction, 2, 2);
         assert_eq!(result, Ok(Point::new(0, 1)));
     }
 
@@ -80,7 +87,7 @@ mod tests {
     fn test_next_point_right_from_start() {
         let point = Point::new(0, 0);
         let direction = Direction::Right;
-        let result = point.next_point(direction, 2);
+        let result = point.next_point(direction, 2, 2);
         assert_eq!(result, Ok(Point::new(1, 0)));
     }
 
@@ -88,7 +95,7 @@ mod tests {
     fn test_next_point_up_from_middle() {
         let point = Point::new(0, 1);
         let direction = Direction::Up;
-        let result = point.next_point(direction, 2);
+        let result = point.next_point(direction, 2, 2);
         assert_eq!(result, Ok(Point::new(0, 0)));
     }
 
@@ -96,7 +103,7 @@ mod tests {
     fn test_next_point_left_from_middle() {
         let point = Point::new(1, 0);
         let direction = Direction::Left;
-        let result = point.next_point(direction, 2);
+        let result = point.next_point(direction, 2, 2);
         assert_eq!(result, Ok(Point::new(0, 0)));
     }
 
@@ -104,7 +111,7 @@ mod tests {
     fn test_next_point_down_from_middle() {
         let point = Point::new(0, 0);
         let direction = Direction::Down;
-        let result = point.next_point(direction, 2);
+        let result = point.next_point(direction, 2, 2);
         assert_eq!(result, Ok(Point::new(0, 1)));
     }
 
@@ -112,7 +119,7 @@ mod tests {
     fn test_next_point_right_from_middle() {
         let point = Point::new(0, 0);
         let direction = Direction::Right;
-        let result = point.next_point(direction, 2);
+        let result = point.next_point(direction, 2, 2);
         assert_eq!(result, Ok(Point::new(1, 0)));
     }
 
@@ -120,7 +127,7 @@ mod tests {
     fn test_next_point_down_from_end_throw_error() {
         let point = Point::new(0, 2);
         let direction = Direction::Down;
-        let result = point.next_point(direction, 2);
+        let result = point.next_point(direction, 2, 2);
         assert_eq!(
             result,
             Err(format!(
@@ -134,7 +141,7 @@ mod tests {
     fn test_next_point_right_from_end_throw_error() {
         let point = Point::new(2, 0);
         let direction = Direction::Right;
-        let result = point.next_point(direction, 2);
+        let result = point.next_point(direction, 2, 2);
         assert_eq!(
             result,
             Err(format!(
@@ -148,7 +155,7 @@ mod tests {
     fn test_next_point_up_from_end() {
         let point = Point::new(0, 2);
         let direction = Direction::Up;
-        let result = point.next_point(direction, 2);
+        let result = point.next_point(direction, 2, 2);
         assert_eq!(result, Ok(Point::new(0, 1)));
     }
 
@@ -156,7 +163,35 @@ mod tests {
     fn test_next_point_left_from_end() {
         let point = Point::new(2, 0);
         let direction = Direction::Left;
-        let result = point.next_point(direction, 2);
+        let result = point.next_point(direction, 2, 2);
         assert_eq!(result, Ok(Point::new(1, 0)));
     }
+
+    #[test]
+    fn test_next_point_right_in_wide_board() {
+        let point = Point::new(3, 0);
+        let result = point.next_point(Direction::Right, 5, 1);
+        assert_eq!(result, Ok(Point::new(4, 0)));
+    }
+
+    #[test]
+    fn test_next_point_down_in_wide_board_throw_error() {
+        let point = Point::new(3, 0);
+        let direction = Direction::Down;
+        let result = point.next_point(direction, 5, 1);
+        assert_eq!(
+            result,
+            Err(format!(
+                "Cannot move {:?} from the {} of the board, it goes out of bounds",
+                direction, point
+            ))
+        );
+    }
+
+    #[test]
+    fn test_next_point_down_in_tall_board() {
+        let point = Point::new(0, 3);
+        let result = point.next_point(Direction::Down, 1, 5);
+        assert_eq!(result, Ok(Point::new(0, 4)));
+    }
 }
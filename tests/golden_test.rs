@@ -0,0 +1,98 @@
+use ej_individual::board_diff;
+use ej_individual::bomberman::Bomberman;
+use ej_individual::maze_header::split_header;
+use std::path::{Path, PathBuf};
+
+// Each case is a maze in tests/cases/<name>.in with its starting points in the header
+// (# start: x,y [x,y ...]) and the expected board, or errors, in <name>.expected
+// Run with UPDATE_GOLDEN=1 to write the expected files from the current output
+// Only a malformed or missing header fails the case without comparing it
+const CASES_DIR: &str = "tests/cases";
+const UPDATE_ENV: &str = "UPDATE_GOLDEN";
+
+fn cases() -> Vec<PathBuf> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(CASES_DIR);
+    let mut cases: Vec<PathBuf> = std::fs::read_dir(&dir)
+        .unwrap_or_else(|e| panic!("error reading {}, context {e}", dir.display()))
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|extension| extension == "in"))
+        .collect();
+    cases.sort();
+    cases
+}
+
+// Play the case and return the board, or its parse or play errors one per line
+// Return an error only if the header is malformed or missing
+fn run_case(contents: &str) -> Result<String, String> {
+    let (start_points, maze) = split_header(contents).map_err(|e| e.to_string())?;
+    if start_points.is_empty() {
+        return Err("missing the header # start: x,y".to_string());
+    }
+    let result =
+        Bomberman::parse_all(maze, false).and_then(|mut game| match start_points.as_slice() {
+            [start] => game.play(*start).map_err(|e| vec![e]),
+            starts => game.play_many(starts),
+        });
+    Ok(result.unwrap_or_else(|errors| {
+        let lines: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        lines.join("\n")
+    }))
+}
+
+// Return true if the expected files should be written, UPDATE_GOLDEN is set to
+// anything but empty or 0
+fn update_enabled() -> bool {
+    std::env::var(UPDATE_ENV).is_ok_and(|value| !value.is_empty() && value != "0")
+}
+
+#[test]
+fn golden_cases() {
+    let update = update_enabled();
+    let cases = cases();
+    assert!(!cases.is_empty(), "no cases found in {CASES_DIR}");
+
+    let mut failures = Vec::new();
+    for case in cases {
+        let name = case.display();
+        let contents = std::fs::read_to_string(&case).unwrap();
+        let actual = match run_case(&contents) {
+            Ok(actual) => actual,
+            Err(error) => {
+                failures.push(format!("{name}: {error}"));
+                continue;
+            }
+        };
+        let expected_path = case.with_extension("expected");
+        if update {
+            std::fs::write(&expected_path, &actual).unwrap();
+            continue;
+        }
+        let Ok(expected) = std::fs::read_to_string(&expected_path) else {
+            failures.push(format!(
+                "{name}: missing {}, run with {UPDATE_ENV}=1 to create it",
+                expected_path.display()
+            ));
+            continue;
+        };
+        if expected != actual {
+            let mut diff = match board_diff::diff(&expected, &actual) {
+                Ok(changes) => changes.iter().map(|change| change.to_string()).collect(),
+                Err(error) => vec![error],
+            };
+            if diff.is_empty() {
+                diff.push("only the whitespace differs".to_string());
+            }
+            failures.push(format!(
+                "{name}: board differs from {}\n  {}",
+                expected_path.display(),
+                diff.join("\n  ")
+            ));
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "{} cases failed, run with {UPDATE_ENV}=1 to accept the new output\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
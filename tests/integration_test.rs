@@ -27,4 +27,4 @@ fn integration_test_example_3() {
     let mut game = Bomberman::new(input).unwrap();
     let board = game.play(Point::new(2, 4)).unwrap();
     assert_eq!(result, board);
-}
\ No newline at end of file
+}